@@ -0,0 +1,151 @@
+use crate::id::{number, Error, Id};
+use std::io::BufRead;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    #[default]
+    Whitespace,
+    Tab,
+    Comma,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Format {
+    pub delimiter: Delimiter,
+    pub header: usize,
+    pub columns: (usize, usize),
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Format {
+            delimiter: Delimiter::Whitespace,
+            header: 0,
+            columns: (0, 1),
+        }
+    }
+}
+
+impl Format {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn delimiter(mut self, delimiter: Delimiter) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    pub fn header(mut self, rows: usize) -> Self {
+        self.header = rows;
+        self
+    }
+
+    pub fn columns(mut self, left: usize, right: usize) -> Self {
+        self.columns = (left, right);
+        self
+    }
+
+    fn field<'a>(&self, line: &'a str, column: usize) -> Option<&'a str> {
+        match self.delimiter {
+            Delimiter::Whitespace => line.split_whitespace().nth(column),
+            Delimiter::Tab => line.split('\t').nth(column).map(str::trim),
+            Delimiter::Comma => line.split(',').nth(column).map(str::trim),
+        }
+    }
+
+    fn value<T: Id>(&self, line: &str, n: usize, column: usize) -> Result<T, Error> {
+        let field = self
+            .field(line, column)
+            .ok_or(Error::MissingColumn { line: n, column })?;
+        match number(field) {
+            Ok(("", v)) => Ok(v),
+            _ => Err(Error::Parse(n)),
+        }
+    }
+
+    pub fn pairs<'a, T: Id>(
+        &'a self,
+        r: impl BufRead + 'a,
+    ) -> impl Iterator<Item = Result<(T, T), Error>> + 'a {
+        r.lines()
+            .enumerate()
+            .skip(self.header)
+            .filter_map(move |(i, line)| {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(e) => return Some(Err(e.into())),
+                };
+                if line.trim().is_empty() {
+                    return None;
+                }
+                let (left, right) = self.columns;
+                Some(
+                    self.value(&line, i + 1, left)
+                        .and_then(|a| Ok((a, self.value(&line, i + 1, right)?))),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect<T: Id>(format: &Format, data: &str) -> Result<Vec<(T, T)>, Error> {
+        format.pairs(data.as_bytes()).collect()
+    }
+
+    #[test]
+    fn test1() {
+        let data = "3   4
+4   3
+
+2   5";
+        let v = collect::<i32>(&Format::default(), data).unwrap();
+        assert_eq!(v, vec![(3, 4), (4, 3), (2, 5)]);
+    }
+
+    #[test]
+    fn test2() {
+        let data = "id,left,note,right
+1, 3 ,a,4
+2,4,b,3";
+        let format = Format::new()
+            .delimiter(Delimiter::Comma)
+            .header(1)
+            .columns(1, 3);
+        let v = collect::<i64>(&format, data).unwrap();
+        assert_eq!(v, vec![(3, 4), (4, 3)]);
+    }
+
+    #[test]
+    fn test3() {
+        let data = "left\tright\textra
+3\t4\tx
+4\t\t3";
+        let format = Format::new().delimiter(Delimiter::Tab).header(1);
+        assert!(matches!(
+            collect::<i32>(&format, data),
+            Err(Error::Parse(3))
+        ));
+        let format = format.columns(2, 0);
+        assert!(matches!(
+            collect::<i32>(&format, data),
+            Err(Error::Parse(2))
+        ));
+    }
+
+    #[test]
+    fn test4() {
+        let data = "1 2 3
+4 5";
+        let format = Format::new().columns(2, 0);
+        assert!(matches!(
+            collect::<u64>(&format, data),
+            Err(Error::MissingColumn { line: 2, column: 2 })
+        ));
+        let format = Format::new().columns(1, 0);
+        assert_eq!(collect::<u64>(&format, data).unwrap(), vec![(2, 1), (5, 4)]);
+    }
+}
@@ -0,0 +1,81 @@
+use nom::{
+    character::complete::{digit1, one_of},
+    combinator::{map_res, opt, recognize},
+    sequence::pair,
+    IResult,
+};
+use std::fmt::{self, Display, Formatter};
+use std::hash::Hash;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    Parse(usize),
+    MissingColumn { line: usize, column: usize },
+    Overflow,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "read error: {e}"),
+            Error::Parse(line) => write!(f, "invalid location ids on line {line}"),
+            Error::MissingColumn { line, column } => {
+                write!(f, "line {line} has no column {column}")
+            }
+            Error::Overflow => write!(f, "result does not fit the integer type"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+pub trait Id: Copy + Ord + Hash + FromStr + TryFrom<usize> {
+    const ZERO: Self;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    fn distance(self, rhs: Self) -> Option<Self> {
+        if self > rhs {
+            self.checked_sub(rhs)
+        } else {
+            rhs.checked_sub(self)
+        }
+    }
+}
+
+macro_rules! id {
+    ($($t:ty),*) => {
+        $(
+            impl Id for $t {
+                const ZERO: Self = 0;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+id!(i32, i64, u64, i128);
+
+pub fn number<T: Id>(s: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(s)
+}
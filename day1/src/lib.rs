@@ -1,2 +1,4 @@
+pub mod format;
+pub mod id;
 pub mod part1;
 pub mod part2;
@@ -1,35 +1,79 @@
-use nom::character::complete::i32;
-use nom::character::complete::line_ending;
-use nom::character::complete::multispace1;
-use nom::multi::separated_list1;
-use nom::IResult;
+use crate::format::Format;
+use crate::id::{Error, Id};
+use std::io::BufRead;
 
-fn parse_line(s: &str) -> IResult<&str, (i32, i32)> {
-    let (s, a) = i32(s)?;
-    let (s, _) = multispace1(s)?;
-    let (s, b) = i32(s)?;
-    Ok((s, (a, b)))
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pair<T> {
+    pub left: T,
+    pub right: T,
+    pub distance: T,
 }
 
-fn parse(s: &str) -> IResult<&str, Vec<(i32, i32)>> {
-    let (s, v) = separated_list1(line_ending, parse_line)(s)?;
-    Ok((s, v))
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report<T> {
+    pub total: T,
+    pub pairs: Vec<Pair<T>>,
+}
+
+impl<T: Id> Report<T> {
+    pub fn top(&self, k: usize) -> Vec<Pair<T>> {
+        let mut v = self.pairs.clone();
+        v.sort_by_key(|p| std::cmp::Reverse(p.distance));
+        v.truncate(k);
+        v
+    }
+}
+
+fn parse<T: Id>(r: impl BufRead, format: &Format) -> Result<(Vec<T>, Vec<T>), Error> {
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    for pair in format.pairs(r) {
+        let (a, b) = pair?;
+        left.push(a);
+        right.push(b);
+    }
+    Ok((left, right))
 }
 
 pub fn solve(s: &str) -> i32 {
-    let (_, v) = parse(s).unwrap();
-    let (mut left, mut right): (Vec<_>, Vec<_>) = v.iter().cloned().unzip();
-    left.sort();
-    right.sort();
-    left.iter()
-        .zip(right.iter())
-        .map(|(a, b)| (a - b).abs())
-        .sum()
+    solve_reader(s.as_bytes()).unwrap()
+}
+
+pub fn solve_reader<T: Id>(r: impl BufRead) -> Result<T, Error> {
+    solve_with(r, &Format::default())
+}
+
+pub fn solve_with<T: Id>(r: impl BufRead, format: &Format) -> Result<T, Error> {
+    Ok(report(r, format)?.total)
+}
+
+pub fn report<T: Id>(r: impl BufRead, format: &Format) -> Result<Report<T>, Error> {
+    let (mut left, mut right) = memprof::phase("parse", || parse::<T>(r, format))?;
+    left.sort_unstable();
+    right.sort_unstable();
+    let pairs = left
+        .into_iter()
+        .zip(right)
+        .map(|(left, right)| {
+            let distance = left.distance(right).ok_or(Error::Overflow)?;
+            Ok(Pair {
+                left,
+                right,
+                distance,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let total = pairs
+        .iter()
+        .try_fold(T::ZERO, |acc, p| acc.checked_add(p.distance))
+        .ok_or(Error::Overflow)?;
+    Ok(Report { total, pairs })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::format::Delimiter;
 
     #[test]
     fn test1() {
@@ -47,4 +91,112 @@ mod tests {
         let data = std::fs::read_to_string("input/input.txt").unwrap();
         assert_eq!(solve(&data), 1765812);
     }
+
+    #[test]
+    fn test3() {
+        let file = std::fs::File::open("input/input.txt").unwrap();
+        let n: i32 = solve_reader(std::io::BufReader::new(file)).unwrap();
+        assert_eq!(n, 1765812);
+    }
+
+    #[test]
+    fn test4() {
+        let data = "2147483647   0
+0   -2147483648";
+        assert!(matches!(
+            solve_reader::<i32>(data.as_bytes()),
+            Err(Error::Overflow)
+        ));
+        assert_eq!(solve_reader::<i64>(data.as_bytes()).unwrap(), 4294967295);
+
+        let data = "2147483647   0
+2147483647   0";
+        assert!(matches!(
+            solve_reader::<i32>(data.as_bytes()),
+            Err(Error::Overflow)
+        ));
+        assert_eq!(solve_reader::<i64>(data.as_bytes()).unwrap(), 4294967294);
+
+        let data = "18446744073709551615   0
+7   7";
+        assert_eq!(solve_reader::<u64>(data.as_bytes()).unwrap(), u64::MAX);
+        assert!(matches!(
+            solve_reader::<i64>(data.as_bytes()),
+            Err(Error::Parse(1))
+        ));
+
+        let data = "18446744073709551615   0
+1   0";
+        assert!(matches!(
+            solve_reader::<u64>(data.as_bytes()),
+            Err(Error::Overflow)
+        ));
+        assert_eq!(solve_reader::<i128>(data.as_bytes()).unwrap(), 1 << 64);
+
+        let data = "-170141183460469231731687303715884105728   0";
+        assert!(matches!(
+            solve_reader::<i128>(data.as_bytes()),
+            Err(Error::Overflow)
+        ));
+        let data = "-170141183460469231731687303715884105727   0";
+        assert_eq!(solve_reader::<i128>(data.as_bytes()).unwrap(), i128::MAX);
+    }
+
+    #[test]
+    fn test5() {
+        let data = "left\tright
+3\t4
+4\t3
+2\t5
+1\t3
+3\t9
+3\t3";
+        let format = Format::new().delimiter(Delimiter::Tab).header(1);
+        assert_eq!(solve_with::<i32>(data.as_bytes(), &format).unwrap(), 11);
+    }
+
+    #[test]
+    fn test6() {
+        let data = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+        let report = report::<i32>(data.as_bytes(), &Format::default()).unwrap();
+        assert_eq!(report.total, 11);
+        let pairs = report
+            .pairs
+            .iter()
+            .map(|p| (p.left, p.right, p.distance))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pairs,
+            vec![
+                (1, 3, 2),
+                (2, 3, 1),
+                (3, 3, 0),
+                (3, 4, 1),
+                (3, 5, 2),
+                (4, 9, 5)
+            ]
+        );
+        let top = report.top(2);
+        assert_eq!(
+            top,
+            vec![
+                Pair {
+                    left: 4,
+                    right: 9,
+                    distance: 5
+                },
+                Pair {
+                    left: 1,
+                    right: 3,
+                    distance: 2
+                }
+            ]
+        );
+        assert_eq!(report.top(10).len(), 6);
+    }
 }
@@ -1,32 +1,80 @@
-use nom::character::complete::i32;
-use nom::character::complete::line_ending;
-use nom::character::complete::multispace1;
-use nom::multi::separated_list1;
-use nom::IResult;
+use crate::format::Format;
+use crate::id::{Error, Id};
+use std::collections::HashMap;
+use std::io::BufRead;
 
-fn parse_line(s: &str) -> IResult<&str, (i32, i32)> {
-    let (s, a) = i32(s)?;
-    let (s, _) = multispace1(s)?;
-    let (s, b) = i32(s)?;
-    Ok((s, (a, b)))
+type Counts<T> = HashMap<T, usize>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Multiplicity<T> {
+    pub value: T,
+    pub left: usize,
+    pub right: usize,
+    pub score: T,
 }
 
-fn parse(s: &str) -> IResult<&str, Vec<(i32, i32)>> {
-    let (s, v) = separated_list1(line_ending, parse_line)(s)?;
-    Ok((s, v))
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report<T> {
+    pub total: T,
+    pub values: Vec<Multiplicity<T>>,
+}
+
+// only the multiplicity of each value matters, so both lists are folded into frequency tables
+// while reading instead of being kept around.
+fn parse<T: Id>(r: impl BufRead, format: &Format) -> Result<(Counts<T>, Counts<T>), Error> {
+    let mut left = HashMap::new();
+    let mut right = HashMap::new();
+    for pair in format.pairs(r) {
+        let (a, b) = pair?;
+        *left.entry(a).or_insert(0) += 1;
+        *right.entry(b).or_insert(0) += 1;
+    }
+    Ok((left, right))
 }
 
 pub fn solve(s: &str) -> i32 {
-    let (_, v) = parse(s).unwrap();
-    let (left, right): (Vec<_>, Vec<_>) = v.iter().cloned().unzip();
-    left.iter()
-        .map(|a| right.iter().filter(|b| a == *b).count() as i32 * (*a))
-        .sum()
+    solve_reader(s.as_bytes()).unwrap()
+}
+
+pub fn solve_reader<T: Id>(r: impl BufRead) -> Result<T, Error> {
+    solve_with(r, &Format::default())
+}
+
+pub fn solve_with<T: Id>(r: impl BufRead, format: &Format) -> Result<T, Error> {
+    Ok(report(r, format)?.total)
+}
+
+pub fn report<T: Id>(r: impl BufRead, format: &Format) -> Result<Report<T>, Error> {
+    let (left, right) = memprof::phase("parse", || parse::<T>(r, format))?;
+    let mut values = left
+        .into_iter()
+        .map(|(value, left)| {
+            let right = *right.get(&value).unwrap_or(&0);
+            let score = left
+                .checked_mul(right)
+                .and_then(|times| T::try_from(times).ok())
+                .and_then(|times| value.checked_mul(times))
+                .ok_or(Error::Overflow)?;
+            Ok(Multiplicity {
+                value,
+                left,
+                right,
+                score,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    values.sort_unstable_by_key(|m| m.value);
+    let total = values
+        .iter()
+        .try_fold(T::ZERO, |acc, m| acc.checked_add(m.score))
+        .ok_or(Error::Overflow)?;
+    Ok(Report { total, values })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::format::Delimiter;
 
     #[test]
     fn test1() {
@@ -44,4 +92,93 @@ mod tests {
         let data = std::fs::read_to_string("input/input.txt").unwrap();
         assert_eq!(solve(&data), 20520794);
     }
+
+    #[test]
+    fn test3() {
+        let file = std::fs::File::open("input/input.txt").unwrap();
+        let n: i64 = solve_reader(std::io::BufReader::new(file)).unwrap();
+        assert_eq!(n, 20520794);
+    }
+
+    #[test]
+    fn test4() {
+        let data = (0..200_000)
+            .map(|i| format!("{}   {}", i % 1000, i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(solve(&data), 99_900_000);
+    }
+
+    #[test]
+    fn test5() {
+        let data = "4611686018427387904   4611686018427387904
+4611686018427387904   1";
+        assert!(matches!(
+            solve_reader::<i64>(data.as_bytes()),
+            Err(Error::Overflow)
+        ));
+        assert_eq!(solve_reader::<u64>(data.as_bytes()).unwrap(), 1 << 63);
+        assert!(matches!(
+            solve_reader::<i32>(data.as_bytes()),
+            Err(Error::Parse(1))
+        ));
+
+        let data = "2147483647   2147483647
+1   2";
+        assert_eq!(solve_reader::<i32>(data.as_bytes()).unwrap(), i32::MAX);
+        let data = "2147483647   2147483647
+2147483647   2147483647";
+        assert!(matches!(
+            solve_reader::<i32>(data.as_bytes()),
+            Err(Error::Overflow)
+        ));
+        assert_eq!(
+            solve_reader::<i64>(data.as_bytes()).unwrap(),
+            4 * i32::MAX as i64
+        );
+
+        let data = "-9223372036854775808   -9223372036854775808";
+        assert_eq!(solve_reader::<i64>(data.as_bytes()).unwrap(), i64::MIN);
+        assert_eq!(
+            solve_reader::<i128>(data.as_bytes()).unwrap(),
+            i64::MIN as i128
+        );
+    }
+
+    #[test]
+    fn test6() {
+        let data = "id,right,left
+a,4,3
+b,3,4
+c,5,2
+d,3,1
+e,9,3
+f,3,3";
+        let format = Format::new()
+            .delimiter(Delimiter::Comma)
+            .header(1)
+            .columns(2, 1);
+        assert_eq!(solve_with::<i32>(data.as_bytes(), &format).unwrap(), 31);
+    }
+
+    #[test]
+    fn test7() {
+        let data = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+        let report = report::<i32>(data.as_bytes(), &Format::default()).unwrap();
+        assert_eq!(report.total, 31);
+        let values = report
+            .values
+            .iter()
+            .map(|m| (m.value, m.left, m.right, m.score))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![(1, 1, 0, 0), (2, 1, 0, 0), (3, 3, 3, 27), (4, 1, 1, 4)]
+        );
+    }
 }
@@ -77,7 +77,7 @@ fn recurse(
 }
 
 pub fn solve(s: &str) -> usize {
-    let (_, data) = parse(Span::new(s)).unwrap();
+    let (_, data) = memprof::phase("parse", || parse(Span::new(s))).unwrap();
     let mut destinations = HashMap::new();
     data.iter()
         .filter(|(_, n)| **n == 0)
@@ -100,7 +100,7 @@ mod tests {
 32019012
 01329801
 10456732";
-        assert_eq!(solve(&data), 81);
+        assert_eq!(solve(data), 81);
     }
 
     #[test]
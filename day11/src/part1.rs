@@ -18,7 +18,7 @@ fn step(cache: &mut HashMap<usize, usize>, n: usize, count: usize) {
         cache.entry(1).and_modify(|v| *v += count).or_insert(count);
     } else {
         let srepr = n.to_string();
-        if srepr.len() % 2 == 0 {
+        if srepr.len().is_multiple_of(2) {
             let a = &srepr[..srepr.len() / 2];
             let b = &srepr[srepr.len() / 2..];
             let a = a.parse::<usize>().unwrap();
@@ -35,7 +35,7 @@ fn step(cache: &mut HashMap<usize, usize>, n: usize, count: usize) {
 }
 
 pub fn solve(s: &str, blinks: usize) -> usize {
-    let mut map = parse(s);
+    let mut map = memprof::phase("parse", || parse(s));
     for _blink in 0..blinks {
         let mut cache = HashMap::new();
         map.iter().for_each(|(value, count)| {
@@ -53,7 +53,7 @@ mod tests {
     #[test]
     fn test1() {
         let data = "125 17";
-        assert_eq!(solve(&data, 25), 55312);
+        assert_eq!(solve(data, 25), 55312);
     }
 
     #[test]
@@ -51,7 +51,7 @@ fn create_region(map: &HashMap<IVec2, char>, start: &IVec2, plant: &char, region
 }
 
 pub fn solve(s: &str) -> i32 {
-    let (_, map) = parse(Span::new(s)).unwrap();
+    let (_, map) = memprof::phase("parse", || parse(Span::new(s))).unwrap();
 
     let mut visited: HashSet<IVec2> = HashSet::new();
     map.iter()
@@ -87,7 +87,7 @@ OXOXO
 OOOOO
 OXOXO
 OOOOO";
-        assert_eq!(solve(&data), 772);
+        assert_eq!(solve(data), 772);
 
         let data = "RRRRIICCFF
 RRRRIICCCF
@@ -99,7 +99,7 @@ VVIIICJJEE
 MIIIIIJJEE
 MIIISIJEEE
 MMMISSJEEE";
-        assert_eq!(solve(&data), 1930);
+        assert_eq!(solve(data), 1930);
     }
 
     #[test]
@@ -72,7 +72,7 @@ fn count_corners(x: &IVec2, positions: &HashSet<IVec2>) -> i32 {
 }
 
 pub fn solve(s: &str) -> i32 {
-    let (_, map) = parse(Span::new(s)).unwrap();
+    let (_, map) = memprof::phase("parse", || parse(Span::new(s))).unwrap();
 
     let mut visited: HashSet<IVec2> = HashSet::new();
     map.iter()
@@ -112,21 +112,21 @@ mod tests {
 BBCD
 BBCC
 EEEC";
-        assert_eq!(solve(&data), 80);
+        assert_eq!(solve(data), 80);
 
         let data = "OOOOO
 OXOXO
 OOOOO
 OXOXO
 OOOOO";
-        assert_eq!(solve(&data), 436);
+        assert_eq!(solve(data), 436);
 
         let data = "EEEEE
 EXXXX
 EEEEE
 EXXXX
 EEEEE";
-        assert_eq!(solve(&data), 236);
+        assert_eq!(solve(data), 236);
 
         let data = "AAAAAA
 AAABBA
@@ -134,7 +134,7 @@ AAABBA
 ABBAAA
 ABBAAA
 AAAAAA";
-        assert_eq!(solve(&data), 368);
+        assert_eq!(solve(data), 368);
 
         let data = "RRRRIICCFF
 RRRRIICCCF
@@ -146,7 +146,7 @@ VVIIICJJEE
 MIIIIIJJEE
 MIIISIJEEE
 MMMISSJEEE";
-        assert_eq!(solve(&data), 1206);
+        assert_eq!(solve(data), 1206);
     }
 
     #[test]
@@ -54,7 +54,7 @@ fn solution(m: &Machine) -> Option<(i32, i32)> {
 }
 
 pub fn solve(s: &str) -> i32 {
-    let (_, machines) = parse(s).unwrap();
+    let (_, machines) = memprof::phase("parse", || parse(s)).unwrap();
     machines
         .iter()
         .filter_map(solution)
@@ -83,7 +83,7 @@ Prize: X=7870, Y=6450
 Button A: X+69, Y+23
 Button B: X+27, Y+71
 Prize: X=18641, Y=10279";
-        assert_eq!(solve(&data), 480);
+        assert_eq!(solve(data), 480);
     }
 
     #[test]
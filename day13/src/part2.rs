@@ -54,7 +54,7 @@ fn solution(m: &Machine) -> Option<(i64, i64)> {
 }
 
 pub fn solve(s: &str) -> i64 {
-    let (_, machines) = parse(s).unwrap();
+    let (_, machines) = memprof::phase("parse", || parse(s)).unwrap();
     machines
         .iter()
         .filter_map(solution)
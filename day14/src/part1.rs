@@ -64,7 +64,7 @@ fn count(r: &[IVec2], Y: i32, X: i32) -> usize {
 #[allow(non_snake_case)]
 pub fn solve(s: &str, Y: i32, X: i32) -> usize {
     let t = 100;
-    let (_, v) = parse(s).unwrap();
+    let (_, v) = memprof::phase("parse", || parse(s)).unwrap();
     let positions = v
         .iter()
         .map(|bot| {
@@ -101,7 +101,7 @@ p=7,3 v=-1,2
 p=2,4 v=2,-3
 p=9,5 v=-3,-3";
 
-        assert_eq!(solve(&data, 7, 11), 12);
+        assert_eq!(solve(data, 7, 11), 12);
     }
 
     #[test]
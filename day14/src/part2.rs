@@ -36,7 +36,7 @@ fn parse(s: &str) -> IResult<&str, Vec<Robot>> {
 
 #[allow(non_snake_case)]
 pub fn solve(s: &str, Y: i32, X: i32) -> i32 {
-    let (_, v) = parse(s).unwrap();
+    let (_, v) = memprof::phase("parse", || parse(s)).unwrap();
     (0..i32::MAX)
         .find(|t| {
             v.iter()
@@ -60,7 +60,7 @@ fn parse(s: Span) -> IResult<Span, (HashMap<IVec2, Type>, Vec<Direction>)> {
 }
 
 pub fn solve(s: &str) -> i32 {
-    let (_, (mut cells, moves)) = parse(Span::new(s)).unwrap();
+    let (_, (mut cells, moves)) = memprof::phase("parse", || parse(Span::new(s))).unwrap();
 
     let (bot, _) = cells.iter_mut().find(|c| *c.1 == Type::Bot).unwrap();
     let mut bot = *bot;
@@ -131,7 +131,7 @@ mod tests {
 ########
 
 <^^>>>vv<v>>v<<";
-        assert_eq!(solve(&data), 2028);
+        assert_eq!(solve(data), 2028);
 
         let data = "##########
 #..O..O.O#
@@ -154,7 +154,7 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 <><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
-        assert_eq!(solve(&data), 10092);
+        assert_eq!(solve(data), 10092);
     }
 
     #[test]
@@ -38,9 +38,7 @@ fn parse_cell(s: Span) -> IResult<Span, Vec<(IVec2, Type)>> {
         '.' => (Type::Air, Type::Air),
         _ => unreachable!(),
     })(s)?;
-    let mut v = Vec::new();
-    v.push((IVec2::new(x, y), t1));
-    v.push((IVec2::new(x, y + 1), t2));
+    let v = vec![(IVec2::new(x, y), t1), (IVec2::new(x, y + 1), t2)];
     Ok((s, v))
 }
 
@@ -144,7 +142,7 @@ fn step_vertical(
         }
     }
 
-    return true;
+    true
 }
 
 fn handle_vertical_movement(cells: &mut HashMap<IVec2, Type>, bot: &mut IVec2, dir: &IVec2) {
@@ -198,7 +196,7 @@ fn handle_vertical_movement(cells: &mut HashMap<IVec2, Type>, bot: &mut IVec2, d
 }
 
 pub fn solve(s: &str) -> i32 {
-    let (_, (mut cells, moves)) = parse(Span::new(s)).unwrap();
+    let (_, (mut cells, moves)) = memprof::phase("parse", || parse(Span::new(s))).unwrap();
 
     let (bot, _) = cells.iter_mut().find(|c| *c.1 == Type::Bot).unwrap();
     let mut bot = *bot;
@@ -273,7 +271,7 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 <><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
-        assert_eq!(solve(&data), 9021);
+        assert_eq!(solve(data), 9021);
     }
 
     #[test]
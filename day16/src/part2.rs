@@ -36,7 +36,7 @@ fn parse(s: Span) -> IResult<Span, (HashSet<IVec2>, IVec2, IVec2)> {
 }
 
 pub fn solve(s: &str) -> usize {
-    let (_, (cells, start, end)) = parse(Span::new(s)).unwrap();
+    let (_, (cells, start, end)) = memprof::phase("parse", || parse(Span::new(s))).unwrap();
     let direction = IVec2::Y;
     let bags = astar_bag(
         &(start, direction),
@@ -48,7 +48,7 @@ fn combo(a: u32, b: u32, c: u32, operand: u32) -> u32 {
 }
 
 pub fn solve(s: &str) -> String {
-    let (_, (mut a, mut b, mut c, v)) = parse(s).unwrap();
+    let (_, (mut a, mut b, mut c, v)) = memprof::phase("parse", || parse(s)).unwrap();
     let mut out = Vec::new();
     let mut i = 0;
     loop {
@@ -0,0 +1,111 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{newline, u64},
+    multi::separated_list1,
+    sequence::{preceded, tuple},
+    IResult,
+};
+
+fn reg_a(s: &str) -> IResult<&str, u64> {
+    let (s, n) = preceded(tag("Register A: "), u64)(s)?;
+    let (s, _) = newline(s)?;
+    Ok((s, n))
+}
+
+fn reg_b(s: &str) -> IResult<&str, u64> {
+    let (s, n) = preceded(tag("Register B: "), u64)(s)?;
+    let (s, _) = newline(s)?;
+    Ok((s, n))
+}
+
+fn reg_c(s: &str) -> IResult<&str, u64> {
+    let (s, n) = preceded(tag("Register C: "), u64)(s)?;
+    let (s, _) = newline(s)?;
+    Ok((s, n))
+}
+
+fn program(s: &str) -> IResult<&str, Vec<u64>> {
+    let (s, _) = newline(s)?;
+    let (s, v) = preceded(tag("Program: "), separated_list1(tag(","), u64))(s)?;
+    Ok((s, v))
+}
+
+fn parse(s: &str) -> IResult<&str, (u64, u64, u64, Vec<u64>)> {
+    let (s, (a, b, c)) = tuple((reg_a, reg_b, reg_c))(s)?;
+    let (s, v) = program(s)?;
+    Ok((s, (a, b, c, v)))
+}
+
+fn combo(a: u64, b: u64, c: u64, operand: u64) -> u64 {
+    match operand {
+        (0..=3) => operand,
+        4 => a,
+        5 => b,
+        6 => c,
+        _ => unreachable!("invalid operand: {operand}"),
+    }
+}
+
+fn run(mut a: u64, mut b: u64, mut c: u64, v: &[u64]) -> Vec<u64> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < v.len() {
+        let opcode = v[i];
+        match opcode {
+            0 => a >>= combo(a, b, c, v[i + 1]),
+            1 => b ^= v[i + 1],
+            2 => b = combo(a, b, c, v[i + 1]) % 8,
+            3 => {
+                if a != 0 {
+                    i = v[i + 1] as usize;
+                    continue;
+                }
+            }
+            4 => b ^= c,
+            5 => out.push(combo(a, b, c, v[i + 1]) % 8),
+            6 => b = a >> combo(a, b, c, v[i + 1]),
+            7 => c = a >> combo(a, b, c, v[i + 1]),
+            _ => unreachable!("invalid opcode: {opcode}"),
+        };
+        i += 2;
+    }
+    out
+}
+
+// every loop iteration consumes the lowest 3 bits of A, so A is rebuilt from the last output
+// backwards: at each step try the 8 possible low bits and keep the ones reproducing the suffix.
+fn search(a: u64, b: u64, c: u64, v: &[u64], n: usize) -> Option<u64> {
+    if n > v.len() {
+        return Some(a);
+    }
+    (0..8)
+        .map(|bits| (a << 3) | bits)
+        .filter(|&next| run(next, b, c, v) == v[v.len() - n..])
+        .find_map(|next| search(next, b, c, v, n + 1))
+}
+
+pub fn solve(s: &str) -> u64 {
+    let (_, (_, b, c, v)) = memprof::phase("parse", || parse(s)).unwrap();
+    search(0, b, c, &v, 1).expect("program should be able to output itself")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let data = "Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0";
+        assert_eq!(solve(data), 117440);
+    }
+
+    #[test]
+    fn test2() {
+        let data = std::fs::read_to_string("input/input.txt").unwrap();
+        assert_eq!(solve(&data), 236555995274861);
+    }
+}
@@ -26,7 +26,7 @@ fn parse(s: &str, n: usize) -> Vec<IVec2> {
 }
 
 pub fn solve(s: &str, width: i32, height: i32, n: usize) -> i32 {
-    let cells = parse(s, n);
+    let cells = memprof::phase("parse", || parse(s, n));
     let start = IVec2::new(0, 0);
     let end = IVec2::new(width - 1, height - 1);
     let xbounds = 0..height;
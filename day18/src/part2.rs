@@ -46,7 +46,7 @@ fn path_exist(cells: &[IVec2], width: i32, height: i32) -> bool {
 }
 
 pub fn solve(s: &str, width: i32, height: i32) -> String {
-    let cells = parse(s);
+    let cells = memprof::phase("parse", || parse(s));
     let mut right = cells.len();
     let mut left = 0;
     let mut c = 0;
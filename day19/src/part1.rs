@@ -34,7 +34,7 @@ fn recurse(pattern: &str, towels: &Vec<&str>, cache: &mut HashMap<String, bool>)
 }
 
 pub fn solve(s: &str) -> usize {
-    let (_, (towels, patterns)) = parse(s).unwrap();
+    let (_, (towels, patterns)) = memprof::phase("parse", || parse(s)).unwrap();
     let mut cache = HashMap::new();
     patterns
         .iter()
@@ -58,7 +58,7 @@ ubwu
 bwurrg
 brgr
 bbrgwb";
-        assert_eq!(solve(&data), 6);
+        assert_eq!(solve(data), 6);
     }
 
     #[test]
@@ -35,7 +35,7 @@ fn recurse(pattern: &str, towels: &Vec<&str>, cache: &mut HashMap<String, usize>
 }
 
 pub fn solve(s: &str) -> usize {
-    let (_, (towels, patterns)) = parse(s).unwrap();
+    let (_, (towels, patterns)) = memprof::phase("parse", || parse(s)).unwrap();
     let mut cache = HashMap::new();
     patterns
         .iter()
@@ -59,7 +59,7 @@ ubwu
 bwurrg
 brgr
 bbrgwb";
-        assert_eq!(solve(&data), 16);
+        assert_eq!(solve(data), 16);
     }
 
     #[test]
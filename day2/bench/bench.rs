@@ -1,5 +1,8 @@
+use day2::policy::SafetyPolicy;
 use day2::*;
 
+mod generate;
+
 fn main() {
     divan::main();
 }
@@ -15,3 +18,29 @@ fn part2() {
     let data = std::fs::read_to_string("input/input.txt").unwrap();
     part2::solve(&data);
 }
+
+#[divan::bench(args = [false, true], sample_count = 10)]
+fn part1_large(bencher: divan::Bencher, par: bool) {
+    let data = generate::generate(200_000);
+    let policy = SafetyPolicy::default();
+    bencher.bench(|| {
+        if par {
+            part1::solve_par(&data, &policy)
+        } else {
+            part1::solve(&data)
+        }
+    });
+}
+
+#[divan::bench(args = [false, true], sample_count = 10)]
+fn part2_large(bencher: divan::Bencher, par: bool) {
+    let data = generate::generate(200_000);
+    let policy = SafetyPolicy::default();
+    bencher.bench(|| {
+        if par {
+            part2::solve_par(&data, &policy)
+        } else {
+            part2::solve(&data)
+        }
+    });
+}
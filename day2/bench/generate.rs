@@ -0,0 +1,26 @@
+pub fn random(seed: &mut u64) -> u64 {
+    *seed = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    *seed
+}
+
+// Reports of five to twelve levels that mostly drift by -1..=3, for tests and benches.
+pub fn generate(lines: usize) -> String {
+    let mut seed = 11u64;
+    (0..lines)
+        .map(|_| {
+            let r = random(&mut seed);
+            let len = 5 + (r >> 61) as usize;
+            let mut level = (r >> 40) as i32 % 50;
+            (0..len)
+                .map(|i| {
+                    level += ((r >> (i * 4)) % 5) as i32 - 1;
+                    level.to_string()
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -1,2 +1,8 @@
 pub mod part1;
 pub mod part2;
+pub mod policy;
+pub mod stream;
+
+#[cfg(test)]
+#[path = "../bench/generate.rs"]
+mod generate;
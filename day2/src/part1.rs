@@ -1,15 +1,11 @@
+use crate::policy::{SafetyPolicy, Violation};
+use crate::stream;
 use nom::bytes::complete::tag;
 use nom::character::complete::i32;
 use nom::character::complete::line_ending;
 use nom::multi::separated_list1;
 use nom::IResult;
-
-#[derive(Debug, PartialEq)]
-enum Interval {
-    Unknown,
-    Decreasing,
-    Increasing,
-}
+use std::io::{self, BufRead};
 
 fn parse_line(s: &str) -> IResult<&str, Vec<i32>> {
     let (s, v) = separated_list1(tag(" "), i32)(s)?;
@@ -22,33 +18,31 @@ fn parse(s: &str) -> IResult<&str, Vec<Vec<i32>>> {
 }
 
 pub fn solve(s: &str) -> usize {
-    let (_, v1) = parse(s).unwrap();
-    v1.iter()
-        .filter(|v2| {
-            let mut interval = Interval::Unknown;
-            v2.windows(2).all(|w| {
-                if w[0] < w[1] {
-                    if interval == Interval::Decreasing {
-                        return false;
-                    }
-                    interval = Interval::Increasing;
-                } else if w[0] > w[1] {
-                    if interval == Interval::Increasing {
-                        return false;
-                    }
-                    interval = Interval::Decreasing;
-                } else {
-                    return false;
-                }
-                w[0].abs_diff(w[1]) <= 3
-            })
-        })
-        .count()
+    solve_with(s, &SafetyPolicy::default())
+}
+
+pub fn solve_with(s: &str, policy: &SafetyPolicy) -> usize {
+    let (_, v1) = memprof::phase("parse", || parse(s)).unwrap();
+    v1.iter().filter(|v2| policy.is_safe(v2)).count()
+}
+
+pub fn diagnostics(s: &str, policy: &SafetyPolicy) -> Vec<Result<(), Violation>> {
+    let (_, v1) = memprof::phase("parse", || parse(s)).unwrap();
+    v1.iter().map(|v2| policy.classify(v2)).collect()
+}
+
+pub fn solve_par(s: &str, policy: &SafetyPolicy) -> usize {
+    stream::count(s, |v| policy.is_safe(v)).unwrap()
+}
+
+pub fn solve_reader(r: impl BufRead, policy: &SafetyPolicy) -> io::Result<usize> {
+    stream::count_reader(r, stream::CHUNK_BYTES, |v| policy.is_safe(v))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::policy::Direction;
 
     #[test]
     fn test1() {
@@ -66,4 +60,49 @@ mod tests {
         let data = std::fs::read_to_string("input/input.txt").unwrap();
         assert_eq!(solve(&data), 432);
     }
+
+    #[test]
+    fn test3() {
+        let data = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+        let policy = SafetyPolicy::default();
+        let v = diagnostics(data, &policy)
+            .into_iter()
+            .map(|r| r.err().map(|e| e.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            v,
+            vec![
+                None,
+                Some("step too large at level 2".to_string()),
+                Some("step too large at level 3".to_string()),
+                Some("direction flip at level 2".to_string()),
+                Some("plateau at level 3".to_string()),
+                None,
+            ]
+        );
+        let policy = policy.steps(1, 4).direction(Direction::Decreasing);
+        assert_eq!(solve_with(data, &policy), 2);
+    }
+
+    #[test]
+    fn test4() {
+        let policy = SafetyPolicy::default();
+        let data = std::fs::read_to_string("input/input.txt").unwrap();
+        assert_eq!(solve_par(&data, &policy), solve(&data));
+
+        let data = crate::generate::generate(20_000);
+        let expected = solve(&data);
+        assert!(expected > 0);
+        assert_eq!(solve_par(&data, &policy), expected);
+        assert_eq!(solve_reader(data.as_bytes(), &policy).unwrap(), expected);
+        assert_eq!(
+            stream::count_reader(data.as_bytes(), 1000, |v| policy.is_safe(v)).unwrap(),
+            expected
+        );
+    }
 }
@@ -1,15 +1,11 @@
+use crate::policy::{Direction, SafetyPolicy};
+use crate::stream;
 use nom::bytes::complete::tag;
 use nom::character::complete::i32;
 use nom::character::complete::line_ending;
 use nom::multi::separated_list1;
 use nom::IResult;
-
-#[derive(Debug, PartialEq)]
-enum Interval {
-    Unknown,
-    Decreasing,
-    Increasing,
-}
+use std::io::{self, BufRead};
 
 fn parse_line(s: &str) -> IResult<&str, Vec<i32>> {
     let (s, v) = separated_list1(tag(" "), i32)(s)?;
@@ -21,38 +17,75 @@ fn parse(s: &str) -> IResult<&str, Vec<Vec<i32>>> {
     Ok((s, v))
 }
 
-fn is_safe(v: &[i32]) -> bool {
-    for i in 0..=v.len() {
-        let mut interval = Interval::Unknown;
-        let mut v = v.to_owned();
-        if i != 0 {
-            v.remove(i - 1);
+// can[i][j] holds the previous kept level when level i is kept after j removals (None when i is
+// the first kept level); a step may only skip up to k levels, so this is O(n * k^2).
+fn removals_in(
+    v: &[i32],
+    k: usize,
+    policy: &SafetyPolicy,
+    direction: Direction,
+) -> Option<Vec<usize>> {
+    let n = v.len();
+    let mut can: Vec<Vec<Option<Option<usize>>>> = vec![vec![None; k + 1]; n];
+    for i in 0..n {
+        if i <= k {
+            can[i][i] = Some(None);
         }
-        if v.windows(2).all(|w| {
-            if w[0] < w[1] {
-                if interval == Interval::Decreasing {
-                    return false;
-                }
-                interval = Interval::Increasing;
-            } else if w[0] > w[1] {
-                if interval == Interval::Increasing {
-                    return false;
+        for p in i.saturating_sub(k + 1)..i {
+            let r = i - 1 - p;
+            if !policy.step_ok(v[p], v[i], direction) {
+                continue;
+            }
+            for j in r..=k {
+                if can[p][j - r].is_some() && can[i][j].is_none() {
+                    can[i][j] = Some(Some(p));
                 }
-                interval = Interval::Decreasing;
-            } else {
-                return false;
             }
-            w[0].abs_diff(w[1]) <= 3
-        }) {
-            return true;
         }
     }
-    false
+    let (mut i, mut j) = (0..n)
+        .flat_map(|i| (0..=k).map(move |j| (i, j)))
+        .filter(|&(i, j)| can[i][j].is_some() && j + n - 1 - i <= k)
+        .min_by_key(|&(i, j)| j + n - 1 - i)?;
+    let mut removed = ((i + 1)..n).collect::<Vec<_>>();
+    while let Some(Some(p)) = can[i][j] {
+        removed.extend(p + 1..i);
+        j -= i - 1 - p;
+        i = p;
+    }
+    removed.extend(0..i);
+    removed.sort_unstable();
+    Some(removed)
+}
+
+pub fn removals(v: &[i32], k: usize, policy: &SafetyPolicy) -> Option<Vec<usize>> {
+    if v.is_empty() {
+        return Some(Vec::new());
+    }
+    policy
+        .directions()
+        .into_iter()
+        .filter_map(|direction| removals_in(v, k, policy, direction))
+        .min_by_key(|r| r.len())
 }
 
 pub fn solve(s: &str) -> usize {
-    let (_, v1) = parse(s).unwrap();
-    v1.iter().filter(|v2| is_safe(v2)).count()
+    solve_with(s, &SafetyPolicy::default())
+}
+
+pub fn solve_with(s: &str, policy: &SafetyPolicy) -> usize {
+    let (_, v1) = memprof::phase("parse", || parse(s)).unwrap();
+    v1.iter()
+        .filter(|v2| removals(v2, 1, policy).is_some())
+        .count()
+}
+
+pub fn solve_par(s: &str, policy: &SafetyPolicy) -> usize {
+    stream::count(s, |v| removals(v, 1, policy).is_some()).unwrap()
+}
+
+pub fn solve_reader(r: impl BufRead, policy: &SafetyPolicy) -> io::Result<usize> {
+    stream::count_reader(r, stream::CHUNK_BYTES, |v| removals(v, 1, policy).is_some())
 }
 
 #[cfg(test)]
@@ -75,4 +108,92 @@ mod tests {
         let data = std::fs::read_to_string("input/input.txt").unwrap();
         assert_eq!(solve(&data), 488);
     }
+
+    fn brute_force(v: &[i32], k: usize) -> Option<usize> {
+        (0..1usize << v.len())
+            .filter(|mask| mask.count_ones() as usize <= k)
+            .filter(|mask| {
+                let kept = (0..v.len())
+                    .filter(|i| mask & (1 << i) == 0)
+                    .map(|i| v[i])
+                    .collect::<Vec<_>>();
+                SafetyPolicy::default().is_safe(&kept)
+            })
+            .map(|mask| mask.count_ones() as usize)
+            .min()
+    }
+
+    #[test]
+    fn test3() {
+        let policy = SafetyPolicy::default();
+        assert_eq!(removals(&[1, 3, 2, 4, 5], 0, &policy), None);
+        assert_eq!(removals(&[1, 3, 2, 4, 5], 1, &policy), Some(vec![2]));
+        assert_eq!(removals(&[8, 6, 4, 4, 1], 1, &policy), Some(vec![3]));
+        assert_eq!(removals(&[1, 2, 7, 8, 9], 1, &policy), None);
+        assert_eq!(removals(&[1, 2, 7, 8, 9], 2, &policy), Some(vec![0, 1]));
+        assert_eq!(
+            removals(&[9, 1, 2, 9, 3, 4, 9], 3, &policy),
+            Some(vec![0, 3, 6])
+        );
+        assert_eq!(removals(&[5], 0, &policy), Some(vec![]));
+
+        let mut seed = 7u64;
+        for _ in 0..2000 {
+            let len = 1 + (seed % 9) as usize;
+            let v = (0..len)
+                .map(|_| (crate::generate::random(&mut seed) >> 60) as i32)
+                .collect::<Vec<_>>();
+            for k in 0..4 {
+                let removed = removals(&v, k, &policy);
+                assert_eq!(
+                    removed.as_ref().map(|r| r.len()),
+                    brute_force(&v, k),
+                    "{v:?} {k}"
+                );
+                if let Some(removed) = removed {
+                    let kept = (0..v.len())
+                        .filter(|i| !removed.contains(i))
+                        .map(|i| v[i])
+                        .collect::<Vec<_>>();
+                    assert!(policy.is_safe(&kept));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test4() {
+        let data = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+        let policy = SafetyPolicy::new().allow_plateaus(true);
+        assert_eq!(solve_with(data, &policy), 4);
+        let policy = SafetyPolicy::new().direction(Direction::Increasing);
+        assert_eq!(solve_with(data, &policy), 2);
+        assert_eq!(
+            removals(&[1, 2, 2, 5], 0, &policy.allow_plateaus(true)),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn test5() {
+        let policy = SafetyPolicy::default();
+        let data = std::fs::read_to_string("input/input.txt").unwrap();
+        assert_eq!(solve_par(&data, &policy), solve(&data));
+
+        let data = crate::generate::generate(20_000);
+        let expected = solve(&data);
+        assert!(expected > 0);
+        assert_eq!(solve_par(&data, &policy), expected);
+        assert_eq!(solve_reader(data.as_bytes(), &policy).unwrap(), expected);
+        assert_eq!(
+            stream::count_reader(data.as_bytes(), 1000, |v| removals(v, 1, &policy).is_some())
+                .unwrap(),
+            expected
+        );
+    }
 }
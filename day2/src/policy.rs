@@ -0,0 +1,179 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    DirectionFlip,
+    WrongDirection,
+    StepTooSmall,
+    StepTooLarge,
+    Plateau,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub index: usize,
+    pub reason: Reason,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let reason = match self.reason {
+            Reason::DirectionFlip => "direction flip",
+            Reason::WrongDirection => "wrong direction",
+            Reason::StepTooSmall => "step too small",
+            Reason::StepTooLarge => "step too large",
+            Reason::Plateau => "plateau",
+        };
+        write!(f, "{reason} at level {}", self.index)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    pub min_step: u32,
+    pub max_step: u32,
+    pub allow_plateaus: bool,
+    pub direction: Option<Direction>,
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        SafetyPolicy {
+            min_step: 1,
+            max_step: 3,
+            allow_plateaus: false,
+            direction: None,
+        }
+    }
+}
+
+impl SafetyPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn steps(mut self, min: u32, max: u32) -> Self {
+        self.min_step = min;
+        self.max_step = max;
+        self
+    }
+
+    pub fn allow_plateaus(mut self, allow: bool) -> Self {
+        self.allow_plateaus = allow;
+        self
+    }
+
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    pub fn directions(&self) -> Vec<Direction> {
+        match self.direction {
+            Some(d) => vec![d],
+            None => vec![Direction::Increasing, Direction::Decreasing],
+        }
+    }
+
+    fn step(
+        &self,
+        a: i32,
+        b: i32,
+        current: Option<Direction>,
+    ) -> Result<Option<Direction>, Reason> {
+        let d = match a.cmp(&b) {
+            Ordering::Less => Direction::Increasing,
+            Ordering::Greater => Direction::Decreasing,
+            Ordering::Equal if self.allow_plateaus => return Ok(current),
+            Ordering::Equal => return Err(Reason::Plateau),
+        };
+        match (self.direction, current) {
+            (Some(required), _) if required != d => return Err(Reason::WrongDirection),
+            (_, Some(current)) if current != d => return Err(Reason::DirectionFlip),
+            _ => {}
+        }
+        let step = a.abs_diff(b);
+        if step < self.min_step {
+            Err(Reason::StepTooSmall)
+        } else if step > self.max_step {
+            Err(Reason::StepTooLarge)
+        } else {
+            Ok(Some(d))
+        }
+    }
+
+    pub fn step_ok(&self, a: i32, b: i32, direction: Direction) -> bool {
+        self.step(a, b, Some(direction)).is_ok()
+    }
+
+    pub fn classify(&self, v: &[i32]) -> Result<(), Violation> {
+        let mut current = None;
+        for (i, w) in v.windows(2).enumerate() {
+            current = self.step(w[0], w[1], current).map_err(|reason| Violation {
+                index: i + 1,
+                reason,
+            })?;
+        }
+        Ok(())
+    }
+
+    pub fn is_safe(&self, v: &[i32]) -> bool {
+        self.classify(v).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reason(policy: &SafetyPolicy, v: &[i32]) -> Option<(usize, Reason)> {
+        policy.classify(v).err().map(|e| (e.index, e.reason))
+    }
+
+    #[test]
+    fn test1() {
+        let policy = SafetyPolicy::default();
+        assert_eq!(reason(&policy, &[7, 6, 4, 2, 1]), None);
+        assert_eq!(
+            reason(&policy, &[1, 2, 7, 8, 9]),
+            Some((2, Reason::StepTooLarge))
+        );
+        assert_eq!(
+            reason(&policy, &[1, 3, 2, 4, 5]),
+            Some((2, Reason::DirectionFlip))
+        );
+        assert_eq!(
+            reason(&policy, &[8, 6, 4, 4, 1]),
+            Some((3, Reason::Plateau))
+        );
+        assert_eq!(reason(&policy, &[]), None);
+        assert_eq!(reason(&policy, &[5]), None);
+    }
+
+    #[test]
+    fn test2() {
+        let policy = SafetyPolicy::new()
+            .steps(2, 4)
+            .allow_plateaus(true)
+            .direction(Direction::Increasing);
+        assert_eq!(reason(&policy, &[1, 3, 3, 7, 9]), None);
+        assert_eq!(reason(&policy, &[1, 3, 4]), Some((2, Reason::StepTooSmall)));
+        assert_eq!(
+            reason(&policy, &[9, 7, 5]),
+            Some((1, Reason::WrongDirection))
+        );
+        assert_eq!(
+            policy.classify(&[1, 6]).unwrap_err().to_string(),
+            "step too large at level 1"
+        );
+        assert!(policy.step_ok(4, 4, Direction::Increasing));
+        assert!(!policy.step_ok(4, 6, Direction::Decreasing));
+    }
+}
@@ -0,0 +1,77 @@
+use nom::character::complete::{i32, space0};
+use nom::multi::fold_many1;
+use nom::sequence::preceded;
+use rayon::prelude::*;
+use std::io::{self, BufRead};
+
+pub const CHUNK_BYTES: usize = 1 << 20;
+
+fn parse_into(line: &str, buf: &mut Vec<i32>) -> io::Result<()> {
+    buf.clear();
+    let r: nom::IResult<&str, ()> =
+        fold_many1(preceded(space0, i32), || (), |(), x| buf.push(x))(line);
+    match r {
+        Ok((rest, ())) if rest.trim().is_empty() => Ok(()),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("malformed report: {line:?}"),
+        )),
+    }
+}
+
+pub fn count<F>(s: &str, f: F) -> io::Result<usize>
+where
+    F: Fn(&[i32]) -> bool + Sync,
+{
+    s.par_lines()
+        .filter(|line| !line.trim().is_empty())
+        .map_init(Vec::new, |buf, line| {
+            parse_into(line, buf)?;
+            Ok(f(buf) as usize)
+        })
+        .sum()
+}
+
+pub fn count_reader<F>(mut r: impl BufRead, chunk_bytes: usize, f: F) -> io::Result<usize>
+where
+    F: Fn(&[i32]) -> bool + Sync,
+{
+    let mut chunk = String::with_capacity(chunk_bytes + 64);
+    let mut total = 0;
+    loop {
+        chunk.clear();
+        while chunk.len() < chunk_bytes && r.read_line(&mut chunk)? > 0 {}
+        if chunk.is_empty() {
+            return Ok(total);
+        }
+        total += count(&chunk, &f)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let data = "1 2\n\n3 4 5\r\n6\n";
+        assert_eq!(count(data, |v| v.len() > 1).unwrap(), 2);
+        assert_eq!(count(data, |v| v.contains(&6)).unwrap(), 1);
+        for chunk_bytes in [1, 4, 100] {
+            assert_eq!(
+                count_reader(data.as_bytes(), chunk_bytes, |v| v.len() > 1).unwrap(),
+                2
+            );
+        }
+    }
+
+    #[test]
+    fn test2() {
+        let data = "1 2\n3 x 4\n5 6";
+        let e = count(data, |_| true).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(e.to_string(), "malformed report: \"3 x 4\"");
+        assert!(count_reader(data.as_bytes(), 4, |_| true).is_err());
+        assert!(count("1 2\n-\n", |_| true).is_err());
+    }
+}
@@ -1,2 +1,2 @@
 pub mod part1;
-// pub mod part2;
+pub mod part2;
@@ -51,7 +51,7 @@ fn race(walls: &HashSet<IVec2>, start: &IVec2, end: &IVec2, ignore: &IVec2) -> i
 }
 
 pub fn solve(s: &str) -> usize {
-    let (_, mut walls) = parse(Span::new(s)).unwrap();
+    let (_, mut walls) = memprof::phase("parse", || parse(Span::new(s))).unwrap();
     let start = *walls
         .iter()
         .find(|(_, c)| **c == 'S')
@@ -0,0 +1,147 @@
+use glam::IVec2;
+use nom::{
+    character::complete::{line_ending, one_of},
+    multi::{many1, separated_list1},
+    IResult,
+};
+use nom_locate::LocatedSpan;
+use pathfinding::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+type Span<'a> = LocatedSpan<&'a str>;
+
+const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
+
+fn parse_cell(s: Span) -> IResult<Span, (IVec2, char)> {
+    let y = s.location_line() as i32 - 1;
+    let x = s.get_column() as i32 - 1;
+    let (s, c) = one_of("#.SE")(s)?;
+    Ok((s, (IVec2::new(x, y), c)))
+}
+
+fn parse(s: Span) -> IResult<Span, (HashSet<IVec2>, IVec2, IVec2)> {
+    let (s, v) = separated_list1(line_ending, many1(parse_cell))(s)?;
+    let mut m = v.into_iter().flatten().collect::<HashMap<IVec2, char>>();
+    let start = *m
+        .iter()
+        .find(|(_, c)| **c == 'S')
+        .expect("start should exist")
+        .0;
+    let end = *m
+        .iter()
+        .find(|(_, c)| **c == 'E')
+        .expect("end should exist")
+        .0;
+    m.retain(|_, c| *c != '#');
+    let track = m.into_keys().collect::<HashSet<IVec2>>();
+    Ok((s, (track, start, end)))
+}
+
+// a cheat jumps from one point of the honest path to a later one through at most `cheat`
+// cells, so it saves the path distance between the two points minus the manhattan distance.
+// Only cheats that save at least one step are counted, whatever the threshold.
+pub fn solve(s: &str, cheat: i32, threshold: usize) -> usize {
+    let (_, (track, start, end)) = memprof::phase("parse", || parse(Span::new(s))).unwrap();
+    let path = bfs(
+        &start,
+        |&pos| {
+            DIRECTIONS
+                .iter()
+                .map(move |dir| pos + dir)
+                .filter(|next| track.contains(next))
+        },
+        |&pos| pos == end,
+    )
+    .expect("path should exist");
+    let threshold = threshold.max(1);
+    let mut count = 0;
+    for (i, a) in path.iter().enumerate() {
+        for (j, b) in path.iter().enumerate().skip(i + threshold) {
+            let distance = (*a - *b).abs().element_sum();
+            if distance <= cheat && j - i - distance as usize >= threshold {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
+
+    fn assert_savings(cheat: i32, savings: &[(usize, usize)]) {
+        for &(saving, count) in savings {
+            assert_eq!(
+                solve(DATA, cheat, saving) - solve(DATA, cheat, saving + 1),
+                count,
+                "cheats saving {saving}"
+            );
+        }
+    }
+
+    #[test]
+    fn test1() {
+        assert_savings(
+            2,
+            &[
+                (2, 14),
+                (4, 14),
+                (6, 2),
+                (8, 4),
+                (10, 2),
+                (12, 3),
+                (20, 1),
+                (36, 1),
+                (38, 1),
+                (40, 1),
+                (64, 1),
+            ],
+        );
+        assert_savings(
+            20,
+            &[
+                (50, 32),
+                (52, 31),
+                (54, 29),
+                (56, 39),
+                (58, 25),
+                (60, 23),
+                (62, 20),
+                (64, 19),
+                (66, 12),
+                (68, 14),
+                (70, 12),
+                (72, 22),
+                (74, 4),
+                (76, 3),
+            ],
+        );
+        assert_eq!(solve(DATA, 20, 50), 285);
+        assert_eq!(solve(DATA, 2, 0), 44);
+        assert_eq!(solve(DATA, 2, 1), 44);
+    }
+
+    #[test]
+    fn test2() {
+        let data = std::fs::read_to_string("input/input.txt").unwrap();
+        assert_eq!(solve(&data, 2, 100), 1296);
+        assert_eq!(solve(&data, 20, 100), 977665);
+    }
+}
@@ -0,0 +1,124 @@
+use glam::IVec2;
+use itertools::*;
+use nom::{
+    character::complete::{alphanumeric1, line_ending},
+    multi::separated_list1,
+    IResult,
+};
+use std::iter::{once, repeat_n};
+
+const NUMERIC_GAP: IVec2 = IVec2::new(0, 3);
+const DIRECTIONAL_GAP: IVec2 = IVec2::new(0, 0);
+
+fn parse(s: &str) -> IResult<&str, Vec<&str>> {
+    let (s, codes) = separated_list1(line_ending, alphanumeric1)(s)?;
+    Ok((s, codes))
+}
+
+fn numeric(c: char) -> IVec2 {
+    match c {
+        '7' => IVec2::new(0, 0),
+        '8' => IVec2::new(1, 0),
+        '9' => IVec2::new(2, 0),
+        '4' => IVec2::new(0, 1),
+        '5' => IVec2::new(1, 1),
+        '6' => IVec2::new(2, 1),
+        '1' => IVec2::new(0, 2),
+        '2' => IVec2::new(1, 2),
+        '3' => IVec2::new(2, 2),
+        '0' => IVec2::new(1, 3),
+        'A' => IVec2::new(2, 3),
+        _ => unreachable!("invalid numeric key: {c}"),
+    }
+}
+
+fn directional(c: char) -> IVec2 {
+    match c {
+        '^' => IVec2::new(1, 0),
+        'A' => IVec2::new(2, 0),
+        '<' => IVec2::new(0, 1),
+        'v' => IVec2::new(1, 1),
+        '>' => IVec2::new(2, 1),
+        _ => unreachable!("invalid directional key: {c}"),
+    }
+}
+
+fn paths(from: IVec2, to: IVec2, gap: IVec2) -> Vec<Vec<char>> {
+    let d = to - from;
+    let h = repeat_n(if d.x > 0 { '>' } else { '<' }, d.x.unsigned_abs() as usize);
+    let v = repeat_n(if d.y > 0 { 'v' } else { '^' }, d.y.unsigned_abs() as usize);
+    let mut paths = Vec::new();
+    if IVec2::new(to.x, from.y) != gap {
+        paths.push(h.clone().chain(v.clone()).chain(once('A')).collect());
+    }
+    if IVec2::new(from.x, to.y) != gap {
+        paths.push(v.chain(h).chain(once('A')).collect());
+    }
+    paths.dedup();
+    paths
+}
+
+fn sequence_cost(sequence: &[char], robots: usize) -> usize {
+    once(&'A')
+        .chain(sequence)
+        .tuple_windows()
+        .map(|(&a, &b)| cost(a, b, robots))
+        .sum()
+}
+
+fn cost(from: char, to: char, robots: usize) -> usize {
+    if robots == 0 {
+        return 1;
+    }
+    paths(directional(from), directional(to), DIRECTIONAL_GAP)
+        .iter()
+        .map(|path| sequence_cost(path, robots - 1))
+        .min()
+        .unwrap()
+}
+
+fn code_cost(code: &str, robots: usize) -> usize {
+    once('A')
+        .chain(code.chars())
+        .tuple_windows()
+        .map(|(a, b)| {
+            paths(numeric(a), numeric(b), NUMERIC_GAP)
+                .iter()
+                .map(|path| sequence_cost(path, robots))
+                .min()
+                .unwrap()
+        })
+        .sum()
+}
+
+pub fn solve(s: &str) -> usize {
+    let (_, codes) = memprof::phase("parse", || parse(s)).unwrap();
+    codes
+        .iter()
+        .map(|code| {
+            let n = code.trim_end_matches('A').parse::<usize>().unwrap();
+            code_cost(code, 2) * n
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let data = "029A
+980A
+179A
+456A
+379A";
+        assert_eq!(solve(data), 126384);
+    }
+
+    #[test]
+    fn test2() {
+        let data = std::fs::read_to_string("input/input.txt").unwrap();
+        assert_eq!(solve(&data), 219366);
+    }
+}
@@ -0,0 +1,53 @@
+use nom::{
+    character::complete::{line_ending, u64},
+    multi::separated_list1,
+    IResult,
+};
+
+fn parse(s: &str) -> IResult<&str, Vec<u64>> {
+    let (s, v) = separated_list1(line_ending, u64)(s)?;
+    Ok((s, v))
+}
+
+fn next(mut n: u64) -> u64 {
+    n = ((n << 6) ^ n) & 0xFFFFFF;
+    n = ((n >> 5) ^ n) & 0xFFFFFF;
+    ((n << 11) ^ n) & 0xFFFFFF
+}
+
+pub fn solve(s: &str) -> u64 {
+    let (_, v) = memprof::phase("parse", || parse(s)).unwrap();
+    v.iter()
+        .map(|&secret| (0..2000).fold(secret, |n, _| next(n)))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let mut n = 123;
+        let expected = [
+            15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432,
+            5908254,
+        ];
+        for e in expected {
+            n = next(n);
+            assert_eq!(n, e);
+        }
+
+        let data = "1
+10
+100
+2024";
+        assert_eq!(solve(data), 37327623);
+    }
+
+    #[test]
+    fn test2() {
+        let data = std::fs::read_to_string("input/input.txt").unwrap();
+        assert_eq!(solve(&data), 18278242420);
+    }
+}
@@ -0,0 +1,81 @@
+use nom::{
+    character::complete::{line_ending, u64},
+    multi::separated_list1,
+    IResult,
+};
+use rayon::prelude::*;
+
+// four price changes in -9..=9 packed as base-19 digits
+const SEQUENCES: usize = 19 * 19 * 19 * 19;
+
+fn parse(s: &str) -> IResult<&str, Vec<u64>> {
+    let (s, v) = separated_list1(line_ending, u64)(s)?;
+    Ok((s, v))
+}
+
+fn next(mut n: u64) -> u64 {
+    n = ((n << 6) ^ n) & 0xFFFFFF;
+    n = ((n >> 5) ^ n) & 0xFFFFFF;
+    ((n << 11) ^ n) & 0xFFFFFF
+}
+
+fn sell(secret: u64, buyer: usize, bananas: &mut [u32], seen: &mut [usize]) {
+    let mut n = secret;
+    let mut price = (n % 10) as usize;
+    let mut key = 0;
+    for i in 0..2000 {
+        n = next(n);
+        let p = (n % 10) as usize;
+        key = (key * 19 + p + 9 - price) % SEQUENCES;
+        price = p;
+        // a buyer sells at the first occurrence of a sequence only
+        if i >= 3 && seen[key] != buyer {
+            seen[key] = buyer;
+            bananas[key] += price as u32;
+        }
+    }
+}
+
+pub fn solve(s: &str) -> u32 {
+    let (_, v) = memprof::phase("parse", || parse(s)).unwrap();
+    v.par_iter()
+        .enumerate()
+        .fold(
+            || (vec![0; SEQUENCES], vec![usize::MAX; SEQUENCES]),
+            |(mut bananas, mut seen), (buyer, &secret)| {
+                sell(secret, buyer, &mut bananas, &mut seen);
+                (bananas, seen)
+            },
+        )
+        .map(|(bananas, _)| bananas)
+        .reduce(
+            || vec![0; SEQUENCES],
+            |mut a, b| {
+                a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+                a
+            },
+        )
+        .into_iter()
+        .max()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let data = "1
+2
+3
+2024";
+        assert_eq!(solve(data), 23);
+    }
+
+    #[test]
+    fn test2() {
+        let data = std::fs::read_to_string("input/input.txt").unwrap();
+        assert_eq!(solve(&data), 2067);
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    edges: HashMap<N, HashSet<N>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Graph {
+            edges: HashMap::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash + Ord> Graph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_edge(&mut self, a: N, b: N) {
+        self.edges.entry(a.clone()).or_default().insert(b.clone());
+        self.edges.entry(b).or_default().insert(a);
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.edges.keys()
+    }
+
+    pub fn neighbors(&self, n: &N) -> impl Iterator<Item = &N> {
+        self.edges.get(n).into_iter().flatten()
+    }
+
+    pub fn connected(&self, a: &N, b: &N) -> bool {
+        self.edges.get(a).is_some_and(|n| n.contains(b))
+    }
+
+    pub fn triangles(&self) -> Vec<[N; 3]> {
+        let mut v = Vec::new();
+        for (a, na) in &self.edges {
+            for b in na.iter().filter(|&b| b > a) {
+                for c in na.intersection(&self.edges[b]).filter(|&c| c > b) {
+                    v.push([a.clone(), b.clone(), c.clone()]);
+                }
+            }
+        }
+        v
+    }
+
+    pub fn max_clique(&self) -> Vec<N> {
+        let mut best = Vec::new();
+        self.bron_kerbosch(
+            &mut Vec::new(),
+            self.edges.keys().cloned().collect(),
+            HashSet::new(),
+            &mut best,
+        );
+        best.sort();
+        best
+    }
+
+    // Bron–Kerbosch with pivoting: neighbours of the pivot are skipped at this level since any
+    // maximal clique containing one of them is found through the pivot or its non-neighbours.
+    fn bron_kerbosch(
+        &self,
+        r: &mut Vec<N>,
+        mut p: HashSet<N>,
+        mut x: HashSet<N>,
+        best: &mut Vec<N>,
+    ) {
+        if p.is_empty() && x.is_empty() {
+            if r.len() > best.len() {
+                *best = r.clone();
+            }
+            return;
+        }
+        let pivot = p
+            .union(&x)
+            .max_by_key(|&u| self.edges[u].intersection(&p).count())
+            .cloned()
+            .unwrap();
+        let candidates = p
+            .difference(&self.edges[&pivot])
+            .cloned()
+            .collect::<Vec<N>>();
+        for v in candidates {
+            let n = &self.edges[&v];
+            r.push(v.clone());
+            self.bron_kerbosch(
+                r,
+                p.intersection(n).cloned().collect(),
+                x.intersection(n).cloned().collect(),
+                best,
+            );
+            r.pop();
+            p.remove(&v);
+            x.insert(v);
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash + Ord> FromIterator<(N, N)> for Graph<N> {
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(iter: I) -> Self {
+        let mut g = Graph::new();
+        for (a, b) in iter {
+            g.add_edge(a, b);
+        }
+        g
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let g = [
+            (1, 2),
+            (2, 3),
+            (3, 1),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (4, 1),
+            (2, 4),
+        ]
+        .into_iter()
+        .collect::<Graph<i32>>();
+        assert!(g.connected(&1, &4));
+        assert!(!g.connected(&1, &5));
+        assert_eq!(g.nodes().count(), 5);
+        assert_eq!(g.neighbors(&5).count(), 2);
+        assert_eq!(g.triangles().len(), 5);
+        assert_eq!(g.max_clique(), vec![1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::graph::Graph;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending},
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult,
+};
+
+fn parse(s: &str) -> IResult<&str, Graph<&str>> {
+    let (s, v) = separated_list1(line_ending, separated_pair(alpha1, tag("-"), alpha1))(s)?;
+    Ok((s, v.into_iter().collect()))
+}
+
+pub fn solve(s: &str) -> String {
+    let (_, graph) = memprof::phase("parse", || parse(s)).unwrap();
+    graph.max_clique().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let data = "kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn";
+        assert_eq!(solve(data), "co,de,ka,ta");
+    }
+
+    #[test]
+    fn test2() {
+        let data = std::fs::read_to_string("input/input.txt").unwrap();
+        assert_eq!(solve(&data), "ay,gu,hs,lc,ma,nf,nu,rc,rd,un,vf,wh,xe");
+    }
+}
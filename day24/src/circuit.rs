@@ -0,0 +1,138 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, line_ending, multispace1, one_of},
+    combinator::value,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, tuple},
+    IResult,
+};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+#[derive(Debug, Clone)]
+pub struct Gate<'a> {
+    pub a: &'a str,
+    pub op: Op,
+    pub b: &'a str,
+    pub out: &'a str,
+}
+
+#[derive(Debug, Clone)]
+pub struct Circuit<'a> {
+    pub wires: HashMap<&'a str, bool>,
+    pub gates: Vec<Gate<'a>>,
+}
+
+fn parse_wire(s: &str) -> IResult<&str, (&str, bool)> {
+    let (s, (name, v)) = separated_pair(alphanumeric1, tag(": "), one_of("01"))(s)?;
+    Ok((s, (name, v == '1')))
+}
+
+fn parse_gate(s: &str) -> IResult<&str, Gate<'_>> {
+    let (s, (a, op, b, out)) = tuple((
+        alphanumeric1,
+        alt((
+            value(Op::And, tag(" AND ")),
+            value(Op::Or, tag(" OR ")),
+            value(Op::Xor, tag(" XOR ")),
+        )),
+        alphanumeric1,
+        preceded(tag(" -> "), alphanumeric1),
+    ))(s)?;
+    Ok((s, Gate { a, op, b, out }))
+}
+
+pub fn parse(s: &str) -> IResult<&str, Circuit<'_>> {
+    let (s, wires) = separated_list1(line_ending, parse_wire)(s)?;
+    let (s, _) = multispace1(s)?;
+    let (s, gates) = separated_list1(line_ending, parse_gate)(s)?;
+    let wires = wires.into_iter().collect();
+    Ok((s, Circuit { wires, gates }))
+}
+
+impl<'a> Circuit<'a> {
+    pub fn simulate(&self) -> HashMap<&'a str, bool> {
+        let mut wires = self.wires.clone();
+        let mut pending = self.gates.iter().collect::<Vec<_>>();
+        while !pending.is_empty() {
+            let before = pending.len();
+            pending.retain(|g| match (wires.get(g.a), wires.get(g.b)) {
+                (Some(&a), Some(&b)) => {
+                    let v = match g.op {
+                        Op::And => a & b,
+                        Op::Or => a | b,
+                        Op::Xor => a ^ b,
+                    };
+                    wires.insert(g.out, v);
+                    false
+                }
+                _ => true,
+            });
+            assert!(pending.len() < before, "circuit should not have loops");
+        }
+        wires
+    }
+
+    pub fn number(wires: &HashMap<&str, bool>, prefix: char) -> u64 {
+        wires
+            .iter()
+            .filter(|(name, v)| name.starts_with(prefix) && **v)
+            .map(|(name, _)| 1 << name[1..].parse::<u64>().unwrap())
+            .sum()
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut s = String::from("digraph circuit {\n");
+        for w in self.wires.keys() {
+            writeln!(s, "  {w} [shape=box, color=blue];").unwrap();
+        }
+        for (i, g) in self.gates.iter().enumerate() {
+            let color = match g.op {
+                Op::And => "green",
+                Op::Or => "orange",
+                Op::Xor => "red",
+            };
+            writeln!(s, "  g{i} [label=\"{:?}\", color={color}];", g.op).unwrap();
+            writeln!(s, "  {} -> g{i};", g.a).unwrap();
+            writeln!(s, "  {} -> g{i};", g.b).unwrap();
+            writeln!(s, "  g{i} -> {};", g.out).unwrap();
+            if g.out.starts_with('z') {
+                writeln!(s, "  {} [shape=box, color=purple];", g.out).unwrap();
+            }
+        }
+        s.push_str("}\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let data = "x00: 1
+x01: 1
+y00: 0
+y01: 1
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+z00 OR z01 -> z02";
+        let (_, circuit) = parse(data).unwrap();
+        let dot = circuit.to_dot();
+        assert!(dot.starts_with("digraph circuit {"));
+        assert!(dot.contains("g2 [label=\"Or\", color=orange];"));
+        assert!(dot.contains("x01 -> g1;"));
+        assert!(dot.contains("g2 -> z02;"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod circuit;
+pub mod part1;
+pub mod part2;
@@ -0,0 +1,159 @@
+use crate::circuit::{parse, Circuit, Op};
+use itertools::*;
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub expected: usize,
+    pub found: Vec<String>,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected {} swapped wires, found {}: {}",
+            self.expected,
+            self.found.len(),
+            self.found.join(",")
+        )
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+fn is_input(w: &str) -> bool {
+    w.starts_with('x') || w.starts_with('y')
+}
+
+// in a ripple-carry adder every bit i > 0 is built as
+//   a = x XOR y, b = x AND y, z = a XOR carry, d = a AND carry, carry' = b OR d
+// so a swapped output shows up as a gate whose output does not fit that shape.
+fn swapped<'a>(circuit: &Circuit<'a>) -> BTreeSet<&'a str> {
+    let last = circuit
+        .gates
+        .iter()
+        .map(|g| g.out)
+        .filter(|w| w.starts_with('z'))
+        .max()
+        .expect("circuit should have outputs");
+    let feeds = |w: &str, op: Op| {
+        circuit
+            .gates
+            .iter()
+            .any(|g| g.op == op && (g.a == w || g.b == w))
+    };
+    let mut wrong = BTreeSet::new();
+    for g in &circuit.gates {
+        let first = g.a.ends_with("00") && g.b.ends_with("00");
+        let inputs = (is_input(g.a), is_input(g.b));
+        let ok = match g.op {
+            _ if g.out.starts_with('z') && g.out != last => {
+                g.op == Op::Xor && (first || inputs == (false, false))
+            }
+            _ if g.out == last => g.op == Op::Or,
+            Op::Xor if inputs != (true, true) => false,
+            Op::Xor => first || feeds(g.out, Op::Xor),
+            Op::And => first || feeds(g.out, Op::Or),
+            Op::Or => feeds(g.out, Op::And) && feeds(g.out, Op::Xor),
+        };
+        if !ok {
+            wrong.insert(g.out);
+        }
+    }
+    wrong
+}
+
+pub fn repair(s: &str, pairs: usize) -> Result<String, Mismatch> {
+    let (_, circuit) = memprof::phase("parse", || parse(s)).unwrap();
+    let wrong = swapped(&circuit);
+    if wrong.len() != pairs * 2 {
+        return Err(Mismatch {
+            expected: pairs * 2,
+            found: wrong.iter().map(|w| w.to_string()).collect(),
+        });
+    }
+    Ok(wrong.iter().join(","))
+}
+
+pub fn solve(s: &str) -> String {
+    repair(s, 4).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let data = std::fs::read_to_string("input/input.txt").unwrap();
+        assert_eq!(solve(&data), "bev,ece,fqa,jlj,uzr,z08,z23,z38");
+    }
+
+    fn adder(bits: usize, swaps: &[(&str, &str)]) -> String {
+        let mut s = String::new();
+        for i in 0..bits {
+            s.push_str(&format!("x{i:02}: 1\ny{i:02}: 0\n"));
+        }
+        let mut gates = vec![
+            (
+                "x00".to_string(),
+                "XOR",
+                "y00".to_string(),
+                "z00".to_string(),
+            ),
+            (
+                "x00".to_string(),
+                "AND",
+                "y00".to_string(),
+                "c00".to_string(),
+            ),
+        ];
+        for i in 1..bits {
+            let (x, y, c) = (
+                format!("x{i:02}"),
+                format!("y{i:02}"),
+                format!("c{:02}", i - 1),
+            );
+            let carry = if i == bits - 1 {
+                format!("z{bits:02}")
+            } else {
+                format!("c{i:02}")
+            };
+            gates.push((x.clone(), "XOR", y.clone(), format!("a{i:02}")));
+            gates.push((x, "AND", y, format!("b{i:02}")));
+            gates.push((format!("a{i:02}"), "XOR", c.clone(), format!("z{i:02}")));
+            gates.push((format!("a{i:02}"), "AND", c, format!("d{i:02}")));
+            gates.push((format!("b{i:02}"), "OR", format!("d{i:02}"), carry));
+        }
+        for (a, op, b, out) in &gates {
+            let out = swaps
+                .iter()
+                .find_map(|&(p, q)| match out.as_str() {
+                    o if o == p => Some(q),
+                    o if o == q => Some(p),
+                    _ => None,
+                })
+                .unwrap_or(out.as_str());
+            s.push_str(&format!("\n{a} {op} {b} -> {out}"));
+        }
+        s
+    }
+
+    #[test]
+    fn test2() {
+        let data = adder(8, &[("c03", "b05"), ("z02", "d02")]);
+        assert_eq!(repair(&data, 2), Ok("b05,c03,d02,z02".to_string()));
+        let e = repair(&data, 4).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "expected 8 swapped wires, found 4: b05,c03,d02,z02"
+        );
+        assert_eq!(repair(&adder(8, &[]), 0), Ok(String::new()));
+        let data = adder(8, &[("z05", "a05")]);
+        assert_eq!(repair(&data, 1), Ok("a05,z05".to_string()));
+        let data = adder(8, &[("z05", "a05"), ("c03", "b05")]);
+        assert_eq!(repair(&data, 2), Ok("a05,b05,c03,z05".to_string()));
+    }
+}
@@ -0,0 +1,98 @@
+use nom::{
+    character::complete::{line_ending, one_of},
+    multi::{many1, separated_list1},
+    sequence::pair,
+    IResult,
+};
+
+type Heights = [usize; 5];
+
+fn parse_schematic(s: &str) -> IResult<&str, Vec<Vec<char>>> {
+    let (s, rows) = separated_list1(line_ending, many1(one_of("#.")))(s)?;
+    Ok((s, rows))
+}
+
+fn parse(s: &str) -> IResult<&str, (Vec<Heights>, Vec<Heights>)> {
+    let (s, schematics) = separated_list1(pair(line_ending, line_ending), parse_schematic)(s)?;
+    let mut locks = Vec::new();
+    let mut keys = Vec::new();
+    for rows in schematics {
+        let mut heights = [0; 5];
+        for (c, h) in heights.iter_mut().enumerate() {
+            *h = rows.iter().filter(|row| row[c] == '#').count() - 1;
+        }
+        if rows[0].iter().all(|&c| c == '#') {
+            locks.push(heights);
+        } else {
+            keys.push(heights);
+        }
+    }
+    Ok((s, (locks, keys)))
+}
+
+pub fn solve(s: &str) -> usize {
+    let (_, (locks, keys)) = memprof::phase("parse", || parse(s)).unwrap();
+    locks
+        .iter()
+        .map(|lock| {
+            keys.iter()
+                .filter(|key| lock.iter().zip(key.iter()).all(|(l, k)| l + k <= 5))
+                .count()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let data = "#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####";
+        assert_eq!(solve(data), 3);
+    }
+
+    #[test]
+    fn test2() {
+        let data = std::fs::read_to_string("input/input.txt").unwrap();
+        assert_eq!(solve(&data), 4806);
+    }
+}
@@ -1,9 +1,48 @@
 use day3::*;
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take};
+use nom::character::complete::i32;
+use nom::combinator::map;
+use nom::multi::many0;
+use nom::sequence::{delimited, separated_pair};
+use nom::IResult;
 
 fn main() {
     divan::main();
 }
 
+fn generate(bytes: usize) -> String {
+    let noise = b"xm%&u[]!@^_+do(n't)l, 0123456789";
+    let mut seed = 3u64;
+    let mut s = String::with_capacity(bytes + 16);
+    while s.len() < bytes {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        match seed >> 58 {
+            0 => s.push_str(&format!(
+                "mul({},{})",
+                (seed >> 20) % 1000,
+                (seed >> 30) % 1000
+            )),
+            1 => s.push_str("do()"),
+            2 => s.push_str("don't()"),
+            _ => s.push(noise[(seed >> 40) as usize % noise.len()] as char),
+        }
+    }
+    s
+}
+
+fn alternation(s: &str) -> IResult<&str, Vec<Option<(i32, i32)>>> {
+    many0(alt((
+        map(
+            delimited(tag("mul("), separated_pair(i32, tag(","), i32), tag(")")),
+            Some,
+        ),
+        map(take(1usize), |_| None),
+    )))(s)
+}
+
 #[divan::bench]
 fn part1() {
     let data = std::fs::read_to_string("input/input.txt").unwrap();
@@ -15,3 +54,30 @@ fn part2() {
     let data = std::fs::read_to_string("input/input.txt").unwrap();
     part2::solve(&data);
 }
+
+#[divan::bench(sample_count = 10)]
+fn part1_large(bencher: divan::Bencher) {
+    let data = generate(16 << 20);
+    bencher.bench(|| part1::solve(&data));
+}
+
+#[divan::bench(sample_count = 10)]
+fn part2_large(bencher: divan::Bencher) {
+    let data = generate(16 << 20);
+    bencher.bench(|| part2::solve(&data));
+}
+
+#[divan::bench(sample_count = 10)]
+fn alternation_large(bencher: divan::Bencher) {
+    let data = generate(16 << 20);
+    bencher.bench(|| {
+        let (_, v) = alternation(&data).unwrap();
+        v.iter().flatten().map(|(a, b)| a * b).sum::<i32>()
+    });
+}
+
+#[divan::bench(sample_count = 10)]
+fn stream_large(bencher: divan::Bencher) {
+    let data = generate(16 << 20);
+    bencher.bench(|| part2::solve_reader(data.as_bytes()).unwrap());
+}
@@ -0,0 +1,248 @@
+use memchr::{memchr, memchr2, memchr3};
+use nom::bytes::complete::{tag, take_while_m_n};
+use nom::character::complete::i32;
+use nom::combinator::{map, map_res};
+use nom::multi::count;
+use nom::sequence::preceded;
+use nom::IResult;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Value(i64),
+    Enable,
+    Disable,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Spec {
+    pub name: &'static str,
+    pub arity: usize,
+    pub eval: fn(&[i64]) -> Effect,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub name: &'static str,
+    pub args: Vec<i64>,
+    pub effect: Effect,
+    pub offset: usize,
+    pub len: usize,
+}
+
+// Arguments are parsed like the other day3 parsers, as signed i32, unless `digits` restricts
+// them to unsigned numbers of a given length.
+#[derive(Debug, Default, Clone)]
+pub struct Registry {
+    specs: Vec<Spec>,
+    digits: Option<(usize, usize)>,
+    first: Vec<u8>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn puzzle() -> Self {
+        Self::new()
+            .register("mul", 2, |v| Effect::Value(v[0] * v[1]))
+            .register("do", 0, |_| Effect::Enable)
+            .register("don't", 0, |_| Effect::Disable)
+    }
+
+    pub fn register(
+        mut self,
+        name: &'static str,
+        arity: usize,
+        eval: fn(&[i64]) -> Effect,
+    ) -> Self {
+        assert!(!name.is_empty(), "instruction names must not be empty");
+        if !self.first.contains(&name.as_bytes()[0]) {
+            self.first.push(name.as_bytes()[0]);
+        }
+        self.specs.push(Spec { name, arity, eval });
+        self
+    }
+
+    // Nine digits keep the product of two arguments within i64.
+    pub fn digits(mut self, min: usize, max: usize) -> Self {
+        self.digits = Some((min, max.min(9)));
+        self
+    }
+
+    fn number<'a>(&self, s: &'a str) -> IResult<&'a str, i64> {
+        match self.digits {
+            Some((min, max)) => map_res(
+                take_while_m_n(min, max, |c: char| c.is_ascii_digit()),
+                str::parse,
+            )(s),
+            None => map(i32, i64::from)(s),
+        }
+    }
+
+    fn args<'a>(&self, s: &'a str, arity: usize) -> IResult<&'a str, Vec<i64>> {
+        if arity == 0 {
+            return Ok((s, Vec::new()));
+        }
+        let (s, first) = self.number(s)?;
+        let (s, mut rest) = count(preceded(tag(","), |s| self.number(s)), arity - 1)(s)?;
+        rest.insert(0, first);
+        Ok((s, rest))
+    }
+
+    fn parse<'a>(&self, s: &'a str, spec: &Spec) -> IResult<&'a str, Vec<i64>> {
+        let (s, _) = tag(spec.name)(s)?;
+        let (s, _) = tag("(")(s)?;
+        let (s, args) = self.args(s, spec.arity)?;
+        let (s, _) = tag(")")(s)?;
+        Ok((s, args))
+    }
+
+    fn candidate(&self, bytes: &[u8]) -> Option<usize> {
+        match self.first[..] {
+            [] => None,
+            [a] => memchr(a, bytes),
+            [a, b] => memchr2(a, b, bytes),
+            [a, b, c] => memchr3(a, b, c, bytes),
+            _ => bytes.iter().position(|b| self.first.contains(b)),
+        }
+    }
+
+    pub fn scan(&self, s: &str) -> Vec<Instruction> {
+        let bytes = s.as_bytes();
+        let mut v = Vec::new();
+        let mut i = 0;
+        while let Some(j) = self.candidate(&bytes[i..]) {
+            i += j;
+            let found = self.specs.iter().find_map(|spec| {
+                let (rest, args) = self.parse(&s[i..], spec).ok()?;
+                Some(Instruction {
+                    name: spec.name,
+                    effect: (spec.eval)(&args),
+                    args,
+                    offset: i,
+                    len: s.len() - i - rest.len(),
+                })
+            });
+            match found {
+                Some(instruction) => {
+                    i += instruction.len;
+                    v.push(instruction);
+                }
+                None => i += 1,
+            }
+        }
+        v
+    }
+}
+
+pub fn execute(v: &[Instruction], conditional: bool) -> Vec<(&Instruction, bool)> {
+    let mut enabled = true;
+    v.iter()
+        .map(|instruction| match instruction.effect {
+            Effect::Enable => {
+                enabled = true;
+                (instruction, true)
+            }
+            Effect::Disable => {
+                enabled = false;
+                (instruction, true)
+            }
+            Effect::Value(_) => (instruction, enabled || !conditional),
+        })
+        .collect()
+}
+
+pub fn total(v: &[Instruction], conditional: bool) -> i64 {
+    execute(v, conditional)
+        .into_iter()
+        .filter_map(|(instruction, executed)| match instruction.effect {
+            Effect::Value(x) if executed => Some(x),
+            _ => None,
+        })
+        .sum()
+}
+
+// Executed instructions are shown in green and recognized but disabled ones in red; the rest of
+// the memory is left as is.
+pub fn highlight(s: &str, v: &[Instruction], conditional: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut last = 0;
+    for (instruction, executed) in execute(v, conditional) {
+        let end = instruction.offset + instruction.len;
+        let color = if executed { 32 } else { 31 };
+        out.push_str(&s[last..instruction.offset]);
+        write!(out, "\x1b[{color}m{}\x1b[0m", &s[instruction.offset..end]).unwrap();
+        last = end;
+    }
+    out.push_str(&s[last..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let data = "mul(1234,5)add(2,3)xneg(7)don't()mul(2,2)do()add(1,1)";
+        let registry = Registry::puzzle()
+            .register("add", 2, |v| Effect::Value(v[0] + v[1]))
+            .register("neg", 1, |v| Effect::Value(-v[0]));
+        let v = registry.clone().digits(1, 3).scan(data);
+        let spans = v
+            .iter()
+            .map(|i| (i.name, i.offset, i.len))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            spans,
+            vec![
+                ("add", 11, 8),
+                ("neg", 20, 6),
+                ("don't", 26, 7),
+                ("mul", 33, 8),
+                ("do", 41, 4),
+                ("add", 45, 8),
+            ]
+        );
+        assert_eq!(total(&v, false), 5 - 7 + 4 + 2);
+        assert_eq!(total(&v, true), 5 - 7 + 2);
+        assert_eq!(registry.scan(data)[0].args, vec![1234, 5]);
+        assert_eq!(registry.digits(1, 4).scan(data)[0].args, vec![1234, 5]);
+        let v = Registry::puzzle()
+            .digits(1, 20)
+            .scan("mul(99999999999,9)mul(999999999,999999999)");
+        assert_eq!(total(&v, false), 999999998000000001);
+    }
+
+    #[test]
+    fn test2() {
+        let data = "xmul(2,4)don't()mul(5,5)";
+        let v = Registry::puzzle().scan(data);
+        assert_eq!(
+            highlight(data, &v, true),
+            "x\x1b[32mmul(2,4)\x1b[0m\x1b[32mdon't()\x1b[0m\x1b[31mmul(5,5)\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test3() {
+        let registry = Registry::puzzle()
+            .register("add", 2, |v| Effect::Value(v[0] + v[1]))
+            .register("sub", 2, |v| Effect::Value(v[0] - v[1]));
+        let v = registry.scan("sub(1,-2)xadd(+3,4)mul(2147483648,1)do()mul(2,2)");
+        let found = v.iter().map(|i| (i.name, i.offset)).collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![("sub", 0), ("add", 10), ("do", 36), ("mul", 40)]
+        );
+        assert_eq!(total(&v, false), 3 + 7 + 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "instruction names must not be empty")]
+    fn test4() {
+        Registry::new().register("", 0, |_| Effect::Enable);
+    }
+}
@@ -1,2 +1,4 @@
+pub mod instruction;
 pub mod part1;
 pub mod part2;
+pub mod stream;
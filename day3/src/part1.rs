@@ -1,10 +1,9 @@
-use nom::branch::alt;
+use crate::stream;
+use memchr::memchr;
 use nom::bytes::complete::tag;
-use nom::bytes::complete::take;
 use nom::character::complete::i32;
-use nom::combinator::map;
-use nom::multi::many0;
 use nom::IResult;
+use std::io::{self, Read};
 
 #[derive(Debug)]
 pub struct Operation {
@@ -12,30 +11,38 @@ pub struct Operation {
     pub b: i32,
 }
 
-fn parse_operation(s: &str) -> IResult<&str, Option<Operation>> {
+fn parse_operation(s: &str) -> IResult<&str, Operation> {
     let (s, _) = tag("mul(")(s)?;
     let (s, a) = i32(s)?;
     let (s, _) = tag(",")(s)?;
     let (s, b) = i32(s)?;
     let (s, _) = tag(")")(s)?;
-    Ok((s, Some(Operation { a, b })))
+    Ok((s, Operation { a, b }))
 }
 
-fn parse(s: &str) -> IResult<&str, Vec<Option<Operation>>> {
-    let (s, operations) = many0(alt((parse_operation, map(take(1usize), |_| None))))(s)?;
-    Ok((s, operations))
+fn parse(s: &str) -> Vec<Operation> {
+    let mut operations = Vec::new();
+    let mut i = 0;
+    while let Some(j) = memchr(b'm', &s.as_bytes()[i..]) {
+        i += j;
+        match parse_operation(&s[i..]) {
+            Ok((rest, o)) => {
+                operations.push(o);
+                i = s.len() - rest.len();
+            }
+            Err(_) => i += 1,
+        }
+    }
+    operations
 }
 
-pub fn solve(s: &str) -> i32 {
-    let (_, v) = parse(s).unwrap();
-    v.iter()
-        .map(|o| {
-            if let Some(o) = o {
-                return o.a * o.b;
-            }
-            0
-        })
-        .sum()
+pub fn solve(s: &str) -> i64 {
+    let v = memprof::phase("parse", || parse(s));
+    v.iter().map(|o| o.a as i64 * o.b as i64).sum()
+}
+
+pub fn solve_reader(r: impl Read) -> io::Result<i64> {
+    stream::run(r, stream::CHUNK_SIZE, false)
 }
 
 #[cfg(test)]
@@ -45,12 +52,31 @@ mod tests {
     #[test]
     fn test1() {
         let data = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-        assert_eq!(solve(&data), 161);
+        assert_eq!(solve(data), 161);
     }
 
     #[test]
     fn test2() {
         let data = std::fs::read_to_string("input/input.txt").unwrap();
         assert_eq!(solve(&data), 171183089);
+        assert_eq!(solve_reader(data.as_bytes()).unwrap(), 171183089);
+        assert_eq!(stream::run(data.as_bytes(), 7, false).unwrap(), 171183089);
+    }
+
+    #[test]
+    fn test3() {
+        let v = parse("mul(mul(2,3)mul(4,5)m");
+        assert_eq!(
+            v.iter().map(|o| (o.a, o.b)).collect::<Vec<_>>(),
+            vec![(2, 3), (4, 5)]
+        );
+        assert!(parse("").is_empty());
+    }
+
+    #[test]
+    fn test4() {
+        let data = "mul(2147483647,2)mul(-2147483648,-1)";
+        assert_eq!(solve(data), 6442450942);
+        assert_eq!(solve_reader(data.as_bytes()).unwrap(), 6442450942);
     }
 }
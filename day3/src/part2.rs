@@ -1,55 +1,15 @@
-use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::bytes::complete::take;
-use nom::character::complete::i32;
-use nom::combinator::map;
-use nom::multi::many0;
-use nom::IResult;
+use crate::instruction::{self, Registry};
+use crate::stream;
+use std::io::{self, Read};
 
-#[derive(Debug)]
-pub enum Token {
-    Operation(i32, i32),
-    Skip,
-    Do,
-    Dont,
+pub fn solve(s: &str) -> i64 {
+    let registry = Registry::puzzle();
+    let v = memprof::phase("parse", || registry.scan(s));
+    instruction::total(&v, true)
 }
 
-fn parse_operation(s: &str) -> IResult<&str, Token> {
-    let (s, _) = tag("mul(")(s)?;
-    let (s, a) = i32(s)?;
-    let (s, _) = tag(",")(s)?;
-    let (s, b) = i32(s)?;
-    let (s, _) = tag(")")(s)?;
-    Ok((s, Token::Operation(a, b)))
-}
-
-fn parse(s: &str) -> IResult<&str, Vec<Token>> {
-    let (s, operations) = many0(alt((
-        parse_operation,
-        map(tag("do()"), |_| Token::Do),
-        map(tag("don't()"), |_| Token::Dont),
-        map(take(1usize), |_| Token::Skip),
-    )))(s)?;
-    Ok((s, operations))
-}
-
-pub fn solve(s: &str) -> i32 {
-    let (_, v) = parse(s).unwrap();
-    let mut res = 0;
-    let mut state = Token::Do;
-    for token in v {
-        match token {
-            Token::Operation(a, b) => {
-                if let Token::Do = state {
-                    res += a * b;
-                }
-            }
-            Token::Do => state = Token::Do,
-            Token::Dont => state = Token::Dont,
-            _ => {}
-        }
-    }
-    res
+pub fn solve_reader(r: impl Read) -> io::Result<i64> {
+    stream::run(r, stream::CHUNK_SIZE, true)
 }
 
 #[cfg(test)]
@@ -59,12 +19,25 @@ mod tests {
     #[test]
     fn test1() {
         let data = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-        assert_eq!(solve(&data), 48);
+        assert_eq!(solve(data), 48);
     }
 
     #[test]
     fn test2() {
         let data = std::fs::read_to_string("input/input.txt").unwrap();
         assert_eq!(solve(&data), 63866497);
+        assert_eq!(solve_reader(data.as_bytes()).unwrap(), 63866497);
+        assert_eq!(stream::run(data.as_bytes(), 7, true).unwrap(), 63866497);
+    }
+
+    #[test]
+    fn test3() {
+        for data in [
+            "mul(1234,5)mul(-2,3)mul(2,3)",
+            "mul(+4,5)don't()mul(-1,-1)do()mul(2147483647,2)mul(2147483648,2)",
+        ] {
+            assert_eq!(solve(data), solve_reader(data.as_bytes()).unwrap());
+        }
+        assert_eq!(solve("mul(1234,5)mul(-2,3)mul(2,3)"), 6170);
     }
 }
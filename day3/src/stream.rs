@@ -0,0 +1,126 @@
+use memchr::memchr2;
+use nom::branch::alt;
+use nom::bytes::streaming::tag;
+use nom::character::streaming::i32;
+use nom::combinator::value;
+use nom::IResult;
+use std::io::{self, Read};
+
+pub const CHUNK_SIZE: usize = 1 << 16;
+
+#[derive(Debug, Clone)]
+enum Instruction {
+    Mul(i32, i32),
+    Do,
+    Dont,
+}
+
+fn parse_mul(s: &[u8]) -> IResult<&[u8], Instruction> {
+    let (s, _) = tag("mul(")(s)?;
+    let (s, a) = i32(s)?;
+    let (s, _) = tag(",")(s)?;
+    let (s, b) = i32(s)?;
+    let (s, _) = tag(")")(s)?;
+    Ok((s, Instruction::Mul(a, b)))
+}
+
+fn parse_instruction(s: &[u8]) -> IResult<&[u8], Instruction> {
+    alt((
+        parse_mul,
+        value(Instruction::Do, tag("do()")),
+        value(Instruction::Dont, tag("don't()")),
+    ))(s)
+}
+
+// The streaming nom parsers report Incomplete when an instruction runs into the end of the
+// buffer, in which case the tail is kept and retried once the next chunk arrives.
+#[derive(Debug)]
+pub struct Machine {
+    conditional: bool,
+    enabled: bool,
+    total: i64,
+    buf: Vec<u8>,
+}
+
+impl Machine {
+    pub fn new(conditional: bool) -> Self {
+        Machine {
+            conditional,
+            enabled: true,
+            total: 0,
+            buf: Vec::new(),
+        }
+    }
+
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.buf.extend_from_slice(chunk);
+        let consumed = self.scan(false);
+        self.buf.drain(..consumed);
+    }
+
+    pub fn finish(mut self) -> i64 {
+        self.scan(true);
+        self.total
+    }
+
+    fn scan(&mut self, eof: bool) -> usize {
+        let mut i = 0;
+        while let Some(j) = memchr2(b'm', b'd', &self.buf[i..]) {
+            i += j;
+            match parse_instruction(&self.buf[i..]) {
+                Ok((rest, instruction)) => {
+                    match instruction {
+                        Instruction::Mul(a, b) if self.enabled || !self.conditional => {
+                            self.total += a as i64 * b as i64;
+                        }
+                        Instruction::Mul(..) => {}
+                        Instruction::Do => self.enabled = true,
+                        Instruction::Dont => self.enabled = false,
+                    }
+                    i = self.buf.len() - rest.len();
+                }
+                Err(nom::Err::Incomplete(_)) if !eof => return i,
+                Err(_) => i += 1,
+            }
+        }
+        self.buf.len()
+    }
+}
+
+pub fn run(mut r: impl Read, chunk_size: usize, conditional: bool) -> io::Result<i64> {
+    let mut machine = Machine::new(conditional);
+    let mut chunk = vec![0; chunk_size];
+    loop {
+        match r.read(&mut chunk) {
+            Ok(0) => return Ok(machine.finish()),
+            Ok(n) => machine.feed(&chunk[..n]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let mut m = Machine::new(true);
+        m.feed(b"xmul(12,");
+        m.feed(b"34)do");
+        m.feed(b"n't()mul(2,2)d");
+        m.feed(b"o()m");
+        m.feed(b"ul(3,3)mul(4");
+        assert_eq!(m.finish(), 12 * 34 + 9);
+    }
+
+    #[test]
+    fn test2() {
+        let data = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        for chunk_size in 1..=data.len() {
+            assert_eq!(run(data.as_bytes(), chunk_size, false).unwrap(), 161);
+            assert_eq!(run(data.as_bytes(), chunk_size, true).unwrap(), 48);
+        }
+    }
+}
@@ -1,17 +1,63 @@
+use day4::dictionary::Dictionary;
 use day4::*;
+use grid::Grid;
 
 fn main() {
     divan::main();
 }
 
+fn random(seed: &mut u64) -> u64 {
+    *seed = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    *seed >> 33
+}
+
+fn generate(size: usize, words: usize) -> (Grid<char>, Vec<String>) {
+    let mut seed = 5u64;
+    let letters = "ABCDEFGHIJKLMNOP".chars().collect::<Vec<_>>();
+    let cells = (0..size * size)
+        .map(|_| letters[random(&mut seed) as usize % letters.len()])
+        .collect();
+    let words = (0..words)
+        .map(|_| {
+            let len = 3 + random(&mut seed) as usize % 4;
+            (0..len)
+                .map(|_| letters[random(&mut seed) as usize % letters.len()])
+                .collect()
+        })
+        .collect();
+    (Grid::from_vec(cells, size), words)
+}
+
 #[divan::bench]
 fn part1() {
     let data = std::fs::read_to_string("input/input.txt").unwrap();
     part1::solve(&data);
 }
 
+#[divan::bench]
+fn part1_dictionary() {
+    let data = std::fs::read_to_string("input/input.txt").unwrap();
+    part1::solve_dictionary(&data);
+}
+
 #[divan::bench]
 fn part2() {
     let data = std::fs::read_to_string("input/input.txt").unwrap();
     part2::solve(&data);
 }
+
+#[divan::bench(sample_count = 10)]
+fn words_per_cell(bencher: divan::Bencher) {
+    let (g, words) = generate(200, 500);
+    let words = words.iter().map(String::as_str).collect::<Vec<_>>();
+    bencher.bench(|| search::find_all(&g, &words).len());
+}
+
+#[divan::bench(sample_count = 10)]
+fn words_dictionary(bencher: divan::Bencher) {
+    let (g, words) = generate(200, 500);
+    let words = words.iter().map(String::as_str).collect::<Vec<_>>();
+    bencher.bench(|| Dictionary::new(&words).find(&g).len());
+}
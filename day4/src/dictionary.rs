@@ -0,0 +1,172 @@
+use crate::search::{Direction, Match};
+use aho_corasick::AhoCorasick;
+use grid::*;
+
+#[derive(Debug)]
+struct Line {
+    direction: Direction,
+    text: String,
+    offsets: Vec<usize>,
+    cells: Vec<(usize, usize)>,
+}
+
+impl Line {
+    fn new(g: &Grid<char>, direction: Direction, cells: Vec<(usize, usize)>) -> Self {
+        let mut text = String::with_capacity(cells.len());
+        let mut offsets = Vec::with_capacity(cells.len());
+        for &(r, c) in &cells {
+            offsets.push(text.len());
+            text.push(g[(r, c)]);
+        }
+        Line {
+            direction,
+            text,
+            offsets,
+            cells,
+        }
+    }
+
+    fn cell(&self, offset: usize) -> (usize, usize) {
+        self.cells[self.offsets.binary_search(&offset).unwrap()]
+    }
+}
+
+fn walk(
+    g: &Grid<char>,
+    (mut r, mut c): (usize, usize),
+    (dr, dc): (isize, isize),
+) -> Vec<(usize, usize)> {
+    let mut cells = Vec::new();
+    while r < g.rows() && c < g.cols() {
+        cells.push((r, c));
+        r = r.wrapping_add_signed(dr);
+        c = c.wrapping_add_signed(dc);
+    }
+    cells
+}
+
+// Every row, column and diagonal is read in both directions, so each of the eight directions
+// is covered by exactly one family of lines.
+fn lines(g: &Grid<char>) -> Vec<Line> {
+    let (rows, cols) = (g.rows(), g.cols());
+    let families = [
+        (
+            Direction::East,
+            Direction::West,
+            (0, 1),
+            (0..rows).map(|r| (r, 0)).collect::<Vec<_>>(),
+        ),
+        (
+            Direction::South,
+            Direction::North,
+            (1, 0),
+            (0..cols).map(|c| (0, c)).collect(),
+        ),
+        (
+            Direction::SouthEast,
+            Direction::NorthWest,
+            (1, 1),
+            (0..rows)
+                .map(|r| (r, 0))
+                .chain((1..cols).map(|c| (0, c)))
+                .collect(),
+        ),
+        (
+            Direction::SouthWest,
+            Direction::NorthEast,
+            (1, -1),
+            (0..cols)
+                .map(|c| (0, c))
+                .chain((1..rows).map(|r| (r, cols - 1)))
+                .collect(),
+        ),
+    ];
+    let mut v = Vec::new();
+    for (forward, backward, step, starts) in families {
+        for start in starts {
+            let cells = walk(g, start, step);
+            let reversed = cells.iter().rev().copied().collect();
+            v.push(Line::new(g, forward, cells));
+            v.push(Line::new(g, backward, reversed));
+        }
+    }
+    v
+}
+
+#[derive(Debug)]
+pub struct Dictionary<'a> {
+    words: Vec<&'a str>,
+    automaton: AhoCorasick,
+}
+
+impl<'a> Dictionary<'a> {
+    pub fn new(words: &[&'a str]) -> Self {
+        let words = words
+            .iter()
+            .copied()
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<_>>();
+        let automaton = AhoCorasick::new(&words).unwrap();
+        Dictionary { words, automaton }
+    }
+
+    // Single letters read the same in every direction and are only reported for the rows.
+    pub fn find(&self, g: &Grid<char>) -> Vec<Match<'a>> {
+        let mut v = Vec::new();
+        for line in lines(g) {
+            for m in self.automaton.find_overlapping_iter(&line.text) {
+                let word = self.words[m.pattern().as_usize()];
+                if word.chars().nth(1).is_none() && line.direction != Direction::East {
+                    continue;
+                }
+                let (row, col) = line.cell(m.start());
+                v.push(Match {
+                    word,
+                    row,
+                    col,
+                    direction: line.direction,
+                });
+            }
+        }
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::find_all;
+
+    #[test]
+    fn test1() {
+        let g = Grid::from_vec("..X...SAMXMS...S..A.A..A.".chars().collect(), 5);
+        let words = ["XMAS", "SAM", "AA", "X", "", "MS", "SAMX"];
+        let mut v = Dictionary::new(&words).find(&g);
+        let mut expected = find_all(&g, &words);
+        v.sort();
+        expected.sort();
+        assert_eq!(v, expected);
+        assert_eq!(v.len(), 10);
+    }
+
+    #[test]
+    fn test2() {
+        let g = Grid::from_vec("ÄBCDÄB".chars().collect(), 3);
+        let v = Dictionary::new(&["BÄ", "ÄB"]).find(&g);
+        let found = v
+            .iter()
+            .map(|m| (m.word, m.row, m.col, m.direction))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("ÄB", 0, 0, Direction::East),
+                ("BÄ", 0, 1, Direction::West),
+                ("ÄB", 1, 1, Direction::East),
+                ("BÄ", 1, 2, Direction::West),
+                ("BÄ", 0, 1, Direction::South),
+                ("ÄB", 1, 1, Direction::North),
+            ]
+        );
+    }
+}
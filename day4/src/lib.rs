@@ -1,2 +1,5 @@
+pub mod dictionary;
 pub mod part1;
 pub mod part2;
+pub mod search;
+pub mod template;
@@ -1,142 +1,7 @@
+use crate::dictionary::Dictionary;
+use crate::search;
 use grid::*;
 
-#[derive(Debug)]
-pub enum Direction {
-    North,
-    NorthEast,
-    East,
-    SouthEast,
-    South,
-    SouthWest,
-    West,
-    NorthWest,
-}
-
-fn word_found(g: &Grid<char>, r: usize, c: usize, d: Direction) -> bool {
-    match d {
-        Direction::North => {
-            if r < 3 {
-                return false;
-            }
-            if let (Some(&x), Some(&m), Some(&a), Some(&s)) = (
-                g.get(r, c),
-                g.get(r - 1, c),
-                g.get(r - 2, c),
-                g.get(r - 3, c),
-            ) {
-                x == 'X' && m == 'M' && a == 'A' && s == 'S'
-            } else {
-                false
-            }
-        }
-        Direction::NorthEast => {
-            if r < 3 || c > g.cols() - 4 {
-                return false;
-            }
-            if let (Some(&x), Some(&m), Some(&a), Some(&s)) = (
-                g.get(r, c),
-                g.get(r - 1, c + 1),
-                g.get(r - 2, c + 2),
-                g.get(r - 3, c + 3),
-            ) {
-                x == 'X' && m == 'M' && a == 'A' && s == 'S'
-            } else {
-                false
-            }
-        }
-        Direction::East => {
-            if c > g.cols() - 4 {
-                return false;
-            }
-            if let (Some(&x), Some(&m), Some(&a), Some(&s)) = (
-                g.get(r, c),
-                g.get(r, c + 1),
-                g.get(r, c + 2),
-                g.get(r, c + 3),
-            ) {
-                x == 'X' && m == 'M' && a == 'A' && s == 'S'
-            } else {
-                false
-            }
-        }
-        Direction::SouthEast => {
-            if r > g.rows() - 4 || c > g.cols() - 4 {
-                return false;
-            }
-            if let (Some(&x), Some(&m), Some(&a), Some(&s)) = (
-                g.get(r, c),
-                g.get(r + 1, c + 1),
-                g.get(r + 2, c + 2),
-                g.get(r + 3, c + 3),
-            ) {
-                x == 'X' && m == 'M' && a == 'A' && s == 'S'
-            } else {
-                false
-            }
-        }
-        Direction::South => {
-            if r > g.rows() - 4 {
-                return false;
-            }
-            if let (Some(&x), Some(&m), Some(&a), Some(&s)) = (
-                g.get(r, c),
-                g.get(r + 1, c),
-                g.get(r + 2, c),
-                g.get(r + 3, c),
-            ) {
-                x == 'X' && m == 'M' && a == 'A' && s == 'S'
-            } else {
-                false
-            }
-        }
-        Direction::SouthWest => {
-            if r > g.rows() - 4 || c < 3 {
-                return false;
-            }
-            if let (Some(&x), Some(&m), Some(&a), Some(&s)) = (
-                g.get(r, c),
-                g.get(r + 1, c - 1),
-                g.get(r + 2, c - 2),
-                g.get(r + 3, c - 3),
-            ) {
-                x == 'X' && m == 'M' && a == 'A' && s == 'S'
-            } else {
-                false
-            }
-        }
-        Direction::West => {
-            if c < 3 {
-                return false;
-            }
-            if let (Some(&x), Some(&m), Some(&a), Some(&s)) = (
-                g.get(r, c),
-                g.get(r, c - 1),
-                g.get(r, c - 2),
-                g.get(r, c - 3),
-            ) {
-                x == 'X' && m == 'M' && a == 'A' && s == 'S'
-            } else {
-                false
-            }
-        }
-        Direction::NorthWest => {
-            if r < 3 || c < 3 {
-                return false;
-            }
-            if let (Some(&x), Some(&m), Some(&a), Some(&s)) = (
-                g.get(r, c),
-                g.get(r - 1, c - 1),
-                g.get(r - 2, c - 2),
-                g.get(r - 3, c - 3),
-            ) {
-                x == 'X' && m == 'M' && a == 'A' && s == 'S'
-            } else {
-                false
-            }
-        }
-    }
-}
-
 fn parse(s: &str) -> Grid<char> {
     let mut tmp = Vec::new();
     let mut cols = 0;
@@ -150,37 +15,13 @@ fn parse(s: &str) -> Grid<char> {
 }
 
 pub fn solve(s: &str) -> usize {
-    let grid = parse(s);
-    let mut count = 0;
-    for r in 0..grid.rows() {
-        for c in 0..grid.cols() {
-            if word_found(&grid, r, c, Direction::North) {
-                count += 1;
-            }
-            if word_found(&grid, r, c, Direction::NorthEast) {
-                count += 1;
-            }
-            if word_found(&grid, r, c, Direction::East) {
-                count += 1;
-            }
-            if word_found(&grid, r, c, Direction::SouthEast) {
-                count += 1;
-            }
-            if word_found(&grid, r, c, Direction::South) {
-                count += 1;
-            }
-            if word_found(&grid, r, c, Direction::SouthWest) {
-                count += 1;
-            }
-            if word_found(&grid, r, c, Direction::West) {
-                count += 1;
-            }
-            if word_found(&grid, r, c, Direction::NorthWest) {
-                count += 1;
-            }
-        }
-    }
-    count
+    let grid = memprof::phase("parse", || parse(s));
+    search::find(&grid, "XMAS").len()
+}
+
+pub fn solve_dictionary(s: &str) -> usize {
+    let grid = memprof::phase("parse", || parse(s));
+    Dictionary::new(&["XMAS"]).find(&grid).len()
 }
 
 #[cfg(test)]
@@ -199,12 +40,14 @@ SMSMSASXSS
 SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX";
-        assert_eq!(solve(&data), 18);
+        assert_eq!(solve(data), 18);
+        assert_eq!(solve_dictionary(data), 18);
     }
 
     #[test]
     fn test2() {
         let data = std::fs::read_to_string("input/input.txt").unwrap();
         assert_eq!(solve(&data), 2464);
+        assert_eq!(solve_dictionary(&data), 2464);
     }
 }
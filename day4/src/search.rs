@@ -0,0 +1,125 @@
+use grid::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::NorthEast => (-1, 1),
+            Direction::East => (0, 1),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (1, 0),
+            Direction::SouthWest => (1, -1),
+            Direction::West => (0, -1),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Match<'a> {
+    pub word: &'a str,
+    pub row: usize,
+    pub col: usize,
+    pub direction: Direction,
+}
+
+fn word_found(g: &Grid<char>, word: &[char], r: usize, c: usize, d: Direction) -> bool {
+    let (dr, dc) = d.delta();
+    word.iter().enumerate().all(|(i, &ch)| {
+        let r = r as isize + dr * i as isize;
+        let c = c as isize + dc * i as isize;
+        g.get(r, c) == Some(&ch)
+    })
+}
+
+// A single letter reads the same in every direction, so it is only reported once per cell, as
+// East.
+pub fn find_all<'a>(g: &Grid<char>, words: &[&'a str]) -> Vec<Match<'a>> {
+    let mut v = Vec::new();
+    for &word in words {
+        let chars = word.chars().collect::<Vec<_>>();
+        let directions = match chars.len() {
+            0 => continue,
+            1 => &[Direction::East][..],
+            _ => &Direction::ALL[..],
+        };
+        for ((row, col), &first) in g.indexed_iter() {
+            if first != chars[0] {
+                continue;
+            }
+            for &direction in directions {
+                if word_found(g, &chars, row, col, direction) {
+                    v.push(Match {
+                        word,
+                        row,
+                        col,
+                        direction,
+                    });
+                }
+            }
+        }
+    }
+    v
+}
+
+pub fn find<'a>(g: &Grid<char>, word: &'a str) -> Vec<Match<'a>> {
+    find_all(g, &[word])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let g = Grid::from_vec("..X...SAMXMS...S..A.A..A.".chars().collect(), 5);
+        let v = find(&g, "XMAS");
+        assert_eq!(
+            v,
+            vec![Match {
+                word: "XMAS",
+                row: 1,
+                col: 4,
+                direction: Direction::West,
+            }]
+        );
+        let v = find_all(&g, &["SAM", "AA", "X", ""]);
+        let found = v
+            .iter()
+            .map(|m| (m.word, m.row, m.col, m.direction))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("SAM", 1, 1, Direction::East),
+                ("AA", 3, 3, Direction::South),
+                ("AA", 4, 3, Direction::North),
+                ("X", 0, 2, Direction::East),
+                ("X", 1, 4, Direction::East),
+            ]
+        );
+    }
+}
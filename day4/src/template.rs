@@ -0,0 +1,143 @@
+use grid::*;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Empty,
+    Ragged(usize),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::Empty => write!(f, "template is empty"),
+            Error::Ragged(row) => write!(f, "template row {row} has a different length"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub row: usize,
+    pub col: usize,
+    pub variant: usize,
+}
+
+// Rows are separated by '/' and '.' matches any cell, e.g. "M.S/.A./M.S" for the X-MAS.
+#[derive(Debug, Clone)]
+pub struct Template {
+    variants: Vec<Grid<Option<char>>>,
+}
+
+impl FromStr for Template {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s.split('/').collect::<Vec<_>>();
+        let cols = rows[0].chars().count();
+        if cols == 0 {
+            return Err(Error::Empty);
+        }
+        let mut cells = Vec::new();
+        for (i, row) in rows.iter().enumerate() {
+            if row.chars().count() != cols {
+                return Err(Error::Ragged(i));
+            }
+            cells.extend(row.chars().map(|c| (c != '.').then_some(c)));
+        }
+        Ok(Template::new(Grid::from_vec(cells, cols)))
+    }
+}
+
+impl Template {
+    pub fn new(mut g: Grid<Option<char>>) -> Self {
+        let mut variants: Vec<Grid<Option<char>>> = Vec::new();
+        for _ in 0..2 {
+            for _ in 0..4 {
+                if !variants.contains(&g) {
+                    variants.push(g.clone());
+                }
+                g.rotate_right();
+            }
+            g.flip_cols();
+        }
+        Template { variants }
+    }
+
+    pub fn variants(&self) -> &[Grid<Option<char>>] {
+        &self.variants
+    }
+
+    fn matches_at(g: &Grid<char>, t: &Grid<Option<char>>, row: usize, col: usize) -> bool {
+        t.indexed_iter().all(|((r, c), cell)| match cell {
+            Some(ch) => g.get(row + r, col + c) == Some(ch),
+            None => true,
+        })
+    }
+
+    pub fn locate(&self, g: &Grid<char>) -> Vec<Placement> {
+        let mut v = Vec::new();
+        for (variant, t) in self.variants.iter().enumerate() {
+            if t.rows() > g.rows() || t.cols() > g.cols() {
+                continue;
+            }
+            for row in 0..=g.rows() - t.rows() {
+                for col in 0..=g.cols() - t.cols() {
+                    if Template::matches_at(g, t, row, col) {
+                        v.push(Placement { row, col, variant });
+                    }
+                }
+            }
+        }
+        v
+    }
+
+    pub fn count(&self, g: &Grid<char>) -> usize {
+        self.locate(g).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        assert_eq!(
+            "M.S/.A./M.S".parse::<Template>().unwrap().variants().len(),
+            4
+        );
+        assert_eq!(
+            ".X./XXX/.X.".parse::<Template>().unwrap().variants().len(),
+            1
+        );
+        assert_eq!("XMAS".parse::<Template>().unwrap().variants().len(), 4);
+        assert_eq!("AB/C.".parse::<Template>().unwrap().variants().len(), 8);
+        assert_eq!("".parse::<Template>().unwrap_err(), Error::Empty);
+        assert_eq!("AB/C".parse::<Template>().unwrap_err(), Error::Ragged(1));
+    }
+
+    #[test]
+    fn test2() {
+        let g = Grid::from_vec("XMAS...X...XXX...X..SAMX.".chars().collect(), 5);
+        let plus = ".X./XXX/.X.".parse::<Template>().unwrap();
+        assert_eq!(
+            plus.locate(&g),
+            vec![Placement {
+                row: 1,
+                col: 1,
+                variant: 0
+            }]
+        );
+        let word = "XMAS".parse::<Template>().unwrap();
+        let found = word
+            .locate(&g)
+            .into_iter()
+            .map(|p| (p.row, p.col, word.variants()[p.variant].rows()))
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec![(0, 0, 1), (4, 0, 1)]);
+    }
+}
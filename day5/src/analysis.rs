@@ -0,0 +1,161 @@
+use crate::part1::parse;
+use crate::rules::Rules;
+use serde::Serialize;
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub pages: usize,
+    pub rules: usize,
+    pub total_order: Option<Vec<u32>>,
+    pub redundant: Vec<(u32, u32)>,
+    pub unruled: Vec<(u32, u32)>,
+    pub components: Vec<Vec<u32>>,
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "pages: {}", self.pages)?;
+        writeln!(f, "rules: {}", self.rules)?;
+        match &self.total_order {
+            Some(order) => writeln!(f, "total order: {order:?}")?,
+            None => writeln!(f, "total order: none")?,
+        }
+        writeln!(f, "redundant rules: {}", self.redundant.len())?;
+        for (a, b) in &self.redundant {
+            writeln!(f, "  {a}|{b}")?;
+        }
+        writeln!(f, "unruled pairs: {}", self.unruled.len())?;
+        for (a, b) in &self.unruled {
+            writeln!(f, "  {a} {b}")?;
+        }
+        writeln!(
+            f,
+            "strongly connected components: {}",
+            self.components.len()
+        )?;
+        for c in self.components.iter().filter(|c| c.len() > 1) {
+            writeln!(f, "  {c:?}")?;
+        }
+        Ok(())
+    }
+}
+
+fn reachable(rules: &Rules, from: u32, skip: Option<(u32, u32)>) -> BTreeSet<u32> {
+    let mut seen = BTreeSet::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(a) = queue.pop_front() {
+        for b in rules.successors(a) {
+            if Some((a, b)) != skip && seen.insert(b) {
+                queue.push_back(b);
+            }
+        }
+    }
+    seen
+}
+
+// A rule counts as redundant when the other rules already imply it. With cyclic rules several
+// redundant rules may depend on each other, so they cannot necessarily all be dropped together.
+pub fn analyze(rules: &Rules) -> Report {
+    let pages = rules.pages();
+    let reach = pages
+        .iter()
+        .map(|&p| reachable(rules, p, None))
+        .collect::<Vec<_>>();
+
+    let mut pairs = rules.pairs().collect::<Vec<_>>();
+    pairs.sort_unstable();
+    let redundant = pairs
+        .iter()
+        .copied()
+        .filter(|&(a, b)| reachable(rules, a, Some((a, b))).contains(&b))
+        .collect();
+
+    let mut unruled = Vec::new();
+    for (i, &a) in pages.iter().enumerate() {
+        for &b in &pages[i + 1..] {
+            if !rules.precedes(a, b) && !rules.precedes(b, a) {
+                unruled.push((a, b));
+            }
+        }
+    }
+
+    let mut components: Vec<Vec<u32>> = Vec::new();
+    for (i, &a) in pages.iter().enumerate() {
+        if components.iter().any(|c| c.contains(&a)) {
+            continue;
+        }
+        let mut c = vec![a];
+        c.extend(
+            reach[i]
+                .iter()
+                .copied()
+                .filter(|&b| b != a && reach[pages.binary_search(&b).unwrap()].contains(&a)),
+        );
+        components.push(c);
+    }
+
+    let acyclic =
+        components.len() == pages.len() && !(0..pages.len()).any(|i| reach[i].contains(&pages[i]));
+    let comparable = (0..pages.len()).all(|i| {
+        (i + 1..pages.len()).all(|j| reach[i].contains(&pages[j]) || reach[j].contains(&pages[i]))
+    });
+    let total_order = (acyclic && comparable).then(|| rules.reorder(&pages).unwrap());
+
+    Report {
+        pages: pages.len(),
+        rules: pairs.len(),
+        total_order,
+        redundant,
+        unruled,
+        components,
+    }
+}
+
+pub fn analyze_input(s: &str) -> Report {
+    let (_, (rules, _)) = memprof::phase("parse", || parse(s)).unwrap();
+    analyze(&rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let rules = [(1, 2), (2, 3), (1, 3), (3, 4)]
+            .into_iter()
+            .collect::<Rules>();
+        let report = analyze(&rules);
+        assert_eq!(report.total_order, Some(vec![1, 2, 3, 4]));
+        assert_eq!(report.redundant, vec![(1, 3)]);
+        assert_eq!(report.unruled, vec![(1, 4), (2, 4)]);
+        assert_eq!(report.components.len(), 4);
+
+        let rules = [(1, 2), (2, 3), (3, 1), (3, 4), (5, 6)]
+            .into_iter()
+            .collect::<Rules>();
+        let report = analyze(&rules);
+        assert_eq!(report.total_order, None);
+        assert!(report.redundant.is_empty());
+        assert_eq!(
+            report.components,
+            vec![vec![1, 2, 3], vec![4], vec![5], vec![6]]
+        );
+        assert!(report.to_string().contains("  [1, 2, 3]\n"));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["components"][0], serde_json::json!([1, 2, 3]));
+        assert_eq!(json["total_order"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test2() {
+        let data = std::fs::read_to_string("input/input.txt").unwrap();
+        let report = analyze_input(&data);
+        assert_eq!(report.total_order, None);
+        assert!(report.unruled.is_empty());
+        assert_eq!(report.components.len(), 1);
+        assert_eq!(report.pages, report.components[0].len());
+    }
+}
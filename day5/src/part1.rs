@@ -1,3 +1,4 @@
+use crate::rules::Rules;
 use nom::bytes::complete::tag;
 use nom::character::complete::line_ending;
 use nom::character::complete::multispace0;
@@ -6,15 +7,13 @@ use nom::combinator::map;
 use nom::multi::separated_list1;
 use nom::sequence::separated_pair;
 use nom::IResult;
-use std::ops::Range;
 
-fn parse(s: &str) -> IResult<&str, (Vec<Range<u32>>, Vec<Vec<u32>>)> {
-    let (s, rules) = separated_list1(
-        line_ending,
-        map(separated_pair(u32, tag("|"), u32), |(start, end)| Range {
-            start,
-            end,
-        }),
+type Input = (Rules, Vec<Vec<u32>>);
+
+pub(crate) fn parse(s: &str) -> IResult<&str, Input> {
+    let (s, rules) = map(
+        separated_list1(line_ending, separated_pair(u32, tag("|"), u32)),
+        Rules::from_iter,
     )(s)?;
 
     let (s, _) = multispace0(s)?;
@@ -24,25 +23,11 @@ fn parse(s: &str) -> IResult<&str, (Vec<Range<u32>>, Vec<Vec<u32>>)> {
     Ok((s, (rules, pages)))
 }
 
-fn rule_ok(rules: &[Range<u32>], pages: &[u32]) -> bool {
-    for i in 0..pages.len() - 1 {
-        for j in i + 1..pages.len() {
-            if !rules
-                .iter()
-                .any(|rule| pages[i] == rule.start && pages[j] == rule.end)
-            {
-                return false;
-            }
-        }
-    }
-    true
-}
-
 pub fn solve(s: &str) -> u32 {
-    let (_, (rules, pages)) = parse(s).unwrap();
+    let (_, (rules, pages)) = memprof::phase("parse", || parse(s)).unwrap();
     pages
         .iter()
-        .filter(|&p| rule_ok(&rules, p))
+        .filter(|p| rules.is_ordered(p))
         .map(|p| {
             let idx = p.len() / 2;
             p[idx]
@@ -1,3 +1,4 @@
+use crate::rules::{Error, Repair, Rules};
 use nom::bytes::complete::tag;
 use nom::character::complete::line_ending;
 use nom::character::complete::multispace0;
@@ -6,15 +7,13 @@ use nom::combinator::map;
 use nom::multi::separated_list1;
 use nom::sequence::separated_pair;
 use nom::IResult;
-use std::ops::Range;
 
-fn parse(s: &str) -> IResult<&str, (Vec<Range<u32>>, Vec<Vec<u32>>)> {
-    let (s, rules) = separated_list1(
-        line_ending,
-        map(separated_pair(u32, tag("|"), u32), |(start, end)| Range {
-            start,
-            end,
-        }),
+type Input = (Rules, Vec<Vec<u32>>);
+
+fn parse(s: &str) -> IResult<&str, Input> {
+    let (s, rules) = map(
+        separated_list1(line_ending, separated_pair(u32, tag("|"), u32)),
+        Rules::from_iter,
     )(s)?;
 
     let (s, _) = multispace0(s)?;
@@ -24,45 +23,28 @@ fn parse(s: &str) -> IResult<&str, (Vec<Range<u32>>, Vec<Vec<u32>>)> {
     Ok((s, (rules, pages)))
 }
 
-fn rule_ok(rules: &[Range<u32>], pages: &[u32]) -> (bool, usize, usize) {
-    for i in 0..pages.len() - 1 {
-        for j in i + 1..pages.len() {
-            if !rules
-                .iter()
-                .any(|rule| pages[i] == rule.start && pages[j] == rule.end)
-            {
-                return (false, i, j);
-            }
-        }
-    }
-    (true, 0, 0)
+pub fn solve(s: &str) -> u32 {
+    solve_checked(s).unwrap()
 }
 
-pub fn solve(s: &str) -> u32 {
-    let (_, (rules, pages)) = parse(s).unwrap();
-    let mut wrong = Vec::new();
-    for mut page in pages {
-        let mut page_ok = true;
-        loop {
-            let (res, i, j) = rule_ok(&rules, &page);
-            if !res {
-                page_ok = false;
-                page.swap(i, j);
-            } else if !page_ok {
-                wrong.push(page);
-                break;
-            } else {
-                break;
-            }
-        }
+pub fn solve_checked(s: &str) -> Result<u32, Error> {
+    let (_, (rules, pages)) = memprof::phase("parse", || parse(s)).unwrap();
+    let mut res = 0;
+    for page in pages.iter().filter(|p| !rules.is_ordered(p)) {
+        let page = rules.reorder(page)?;
+        res += page[page.len() / 2];
     }
-    wrong
+    Ok(res)
+}
+
+pub fn diagnose(s: &str) -> Result<Vec<(usize, Repair)>, Error> {
+    let (_, (rules, pages)) = memprof::phase("parse", || parse(s)).unwrap();
+    pages
         .iter()
-        .map(|p| {
-            let idx = p.len() / 2;
-            p[idx]
-        })
-        .sum()
+        .enumerate()
+        .filter(|(_, p)| !rules.is_ordered(p))
+        .map(|(i, p)| Ok((i, rules.repair(p)?)))
+        .collect()
 }
 
 #[cfg(test)]
@@ -100,6 +82,12 @@ mod tests {
 61,13,29
 97,13,75,29,47";
         assert_eq!(solve(data), 123);
+        let v = diagnose(data)
+            .unwrap()
+            .into_iter()
+            .map(|(i, r)| (i, r.violations.len(), r.moves()))
+            .collect::<Vec<_>>();
+        assert_eq!(v, vec![(3, 1, 1), (4, 1, 1), (5, 4, 2)]);
     }
 
     #[test]
@@ -107,4 +95,16 @@ mod tests {
         let data = std::fs::read_to_string("input/input.txt").unwrap();
         assert_eq!(solve(&data), 5093);
     }
+
+    #[test]
+    fn test3() {
+        let data = "1|2
+2|3
+3|1
+
+4,1,2
+2,3,1,4";
+        let e = solve_checked(data).unwrap_err();
+        assert_eq!(e.to_string(), "rules form a cycle: 3 -> 1 -> 2 -> 3");
+    }
 }
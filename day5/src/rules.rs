@@ -0,0 +1,376 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Cycle(Vec<u32>),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::Cycle(pages) => {
+                write!(f, "rules form a cycle: ")?;
+                for p in pages {
+                    write!(f, "{p} -> ")?;
+                }
+                write!(f, "{}", pages[0])
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub before: u32,
+    pub after: u32,
+    pub before_pos: usize,
+    pub after_pos: usize,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}|{} ({} at {}, {} at {})",
+            self.before, self.after, self.before, self.before_pos, self.after, self.after_pos
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repair {
+    pub violations: Vec<Violation>,
+    pub kept: Vec<u32>,
+    pub moved: Vec<u32>,
+    pub order: Vec<u32>,
+}
+
+impl Repair {
+    pub fn moves(&self) -> usize {
+        self.moved.len()
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Rules {
+    after: HashMap<u32, HashSet<u32>>,
+}
+
+impl Rules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, before: u32, after: u32) {
+        self.after.entry(before).or_default().insert(after);
+    }
+
+    pub fn pages(&self) -> Vec<u32> {
+        let mut v = self
+            .after
+            .iter()
+            .flat_map(|(&a, s)| s.iter().copied().chain([a]))
+            .collect::<Vec<_>>();
+        v.sort_unstable();
+        v.dedup();
+        v
+    }
+
+    pub fn pairs(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.after
+            .iter()
+            .flat_map(|(&a, s)| s.iter().map(move |&b| (a, b)))
+    }
+
+    pub fn successors(&self, a: u32) -> impl Iterator<Item = u32> + '_ {
+        self.after.get(&a).into_iter().flatten().copied()
+    }
+
+    pub fn precedes(&self, a: u32, b: u32) -> bool {
+        self.after.get(&a).is_some_and(|s| s.contains(&b))
+    }
+
+    pub fn is_ordered(&self, pages: &[u32]) -> bool {
+        (0..pages.len()).all(|i| (i + 1..pages.len()).all(|j| !self.precedes(pages[j], pages[i])))
+    }
+
+    pub fn reorder(&self, pages: &[u32]) -> Result<Vec<u32>, Error> {
+        let (order, done) = kahn(pages, |i, j| self.precedes(pages[i], pages[j]));
+        if order.len() == pages.len() {
+            return Ok(order);
+        }
+        Err(Error::Cycle(self.cycle(pages, &done)))
+    }
+
+    pub fn violations(&self, pages: &[u32]) -> Vec<Violation> {
+        let mut v = Vec::new();
+        for i in 0..pages.len() {
+            for j in i + 1..pages.len() {
+                if self.precedes(pages[j], pages[i]) {
+                    v.push(Violation {
+                        before: pages[j],
+                        after: pages[i],
+                        before_pos: j,
+                        after_pos: i,
+                    });
+                }
+            }
+        }
+        v
+    }
+
+    // Two kept pages must not be reversed under the rules followed transitively through the
+    // update's pages; every other page is moved once. The reversed pairs form a partial order, so
+    // the most pages that can stay is its largest antichain, which König's theorem reads off a
+    // maximum matching.
+    pub fn repair(&self, pages: &[u32]) -> Result<Repair, Error> {
+        self.reorder(pages)?;
+        let n = pages.len();
+        let mut reach = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| self.precedes(pages[i], pages[j]))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    reach[i][j] |= reach[i][k] && reach[k][j];
+                }
+            }
+        }
+        let reversed = |i: usize, j: usize| i < j && reach[j][i];
+
+        let mut matched = vec![None; n];
+        for i in 0..n {
+            augment(i, &reversed, &mut vec![false; n], &mut matched);
+        }
+        let mut left = (0..n)
+            .map(|i| !matched.contains(&Some(i)))
+            .collect::<Vec<_>>();
+        let mut right = vec![false; n];
+        let mut queue = (0..n).filter(|&i| left[i]).collect::<Vec<_>>();
+        while let Some(i) = queue.pop() {
+            for j in 0..n {
+                if !reversed(i, j) || right[j] {
+                    continue;
+                }
+                right[j] = true;
+                if let Some(k) = matched[j].filter(|&k| !left[k]) {
+                    left[k] = true;
+                    queue.push(k);
+                }
+            }
+        }
+        let keep = (0..n).map(|i| left[i] && !right[i]).collect::<Vec<_>>();
+
+        let (order, _) = kahn(pages, |i, j| reach[i][j] || (i < j && keep[i] && keep[j]));
+        let (kept, moved) = (0..n).partition::<Vec<_>, _>(|&i| keep[i]);
+        Ok(Repair {
+            violations: self.violations(pages),
+            kept: kept.into_iter().map(|i| pages[i]).collect(),
+            moved: moved.into_iter().map(|i| pages[i]).collect(),
+            order,
+        })
+    }
+
+    // Every page left over by Kahn's algorithm still has a predecessor that is left over as well,
+    // so walking predecessors must eventually revisit a page.
+    fn cycle(&self, pages: &[u32], done: &[bool]) -> Vec<u32> {
+        let left = (0..pages.len())
+            .filter(|&i| !done[i])
+            .map(|i| pages[i])
+            .collect::<Vec<_>>();
+        let mut path = vec![left[0]];
+        loop {
+            let last = *path.last().unwrap();
+            let prev = *left.iter().find(|&&p| self.precedes(p, last)).unwrap();
+            if let Some(start) = path.iter().position(|&p| p == prev) {
+                let mut cycle = path.split_off(start);
+                cycle.reverse();
+                return cycle;
+            }
+            path.push(prev);
+        }
+    }
+}
+
+// Kahn's algorithm over the update's pages; ties keep the update's own order so pages without
+// rules between them do not move.
+fn kahn(pages: &[u32], edge: impl Fn(usize, usize) -> bool) -> (Vec<u32>, Vec<bool>) {
+    let n = pages.len();
+    let mut indegree = vec![0; n];
+    for i in 0..n {
+        for (j, d) in indegree.iter_mut().enumerate() {
+            *d += edge(i, j) as usize;
+        }
+    }
+    let mut done = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    while let Some(i) = (0..n).find(|&i| !done[i] && indegree[i] == 0) {
+        done[i] = true;
+        order.push(pages[i]);
+        for (j, d) in indegree.iter_mut().enumerate() {
+            *d -= edge(i, j) as usize;
+        }
+    }
+    (order, done)
+}
+
+fn augment(
+    i: usize,
+    edge: &impl Fn(usize, usize) -> bool,
+    seen: &mut [bool],
+    matched: &mut [Option<usize>],
+) -> bool {
+    for j in 0..matched.len() {
+        if edge(i, j) && !seen[j] {
+            seen[j] = true;
+            if matched[j].is_none_or(|k| augment(k, edge, seen, matched)) {
+                matched[j] = Some(i);
+                return true;
+            }
+        }
+    }
+    false
+}
+
+impl FromIterator<(u32, u32)> for Rules {
+    fn from_iter<I: IntoIterator<Item = (u32, u32)>>(iter: I) -> Self {
+        let mut rules = Rules::new();
+        for (a, b) in iter {
+            rules.add(a, b);
+        }
+        rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let rules = [(1, 2), (2, 3), (3, 4), (4, 2), (5, 1)]
+            .into_iter()
+            .collect::<Rules>();
+        assert!(rules.is_ordered(&[5, 1, 2, 3]));
+        assert!(!rules.is_ordered(&[1, 5]));
+        assert!(rules.is_ordered(&[9, 8]));
+        assert_eq!(rules.reorder(&[2, 9, 1, 5]), Ok(vec![9, 5, 1, 2]));
+        let e = rules.reorder(&[1, 3, 2, 4, 5]).unwrap_err();
+        assert_eq!(e, Error::Cycle(vec![4, 2, 3]));
+        assert_eq!(e.to_string(), "rules form a cycle: 4 -> 2 -> 3 -> 4");
+    }
+
+    #[test]
+    fn test2() {
+        let rules = [(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]
+            .into_iter()
+            .collect::<Rules>();
+        let r = rules.repair(&[2, 3, 4, 1]).unwrap();
+        assert_eq!(r.moves(), 1);
+        assert_eq!(r.kept, vec![2, 3, 4]);
+        assert_eq!(r.moved, vec![1]);
+        assert_eq!(r.order, vec![1, 2, 3, 4]);
+        let v = r
+            .violations
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            v,
+            vec![
+                "1|2 (1 at 3, 2 at 0)",
+                "1|3 (1 at 3, 3 at 1)",
+                "1|4 (1 at 3, 4 at 2)"
+            ]
+        );
+        let r = rules.repair(&[4, 3, 2, 1]).unwrap();
+        assert_eq!(r.moves(), 3);
+        assert_eq!(r.violations.len(), 6);
+        let r = rules.repair(&[1, 2, 3]).unwrap();
+        assert_eq!(r.moves(), 0);
+        assert!(r.violations.is_empty());
+    }
+
+    fn random(seed: &mut u64) -> u64 {
+        *seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        *seed
+    }
+
+    fn permutations(v: &[u32]) -> Vec<Vec<u32>> {
+        if v.is_empty() {
+            return vec![Vec::new()];
+        }
+        let mut out = Vec::new();
+        for i in 0..v.len() {
+            let mut rest = v.to_vec();
+            let first = rest.remove(i);
+            for mut p in permutations(&rest) {
+                p.insert(0, first);
+                out.push(p);
+            }
+        }
+        out
+    }
+
+    fn common(a: &[u32], b: &[u32]) -> usize {
+        let mut len = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for i in 0..a.len() {
+            for j in 0..b.len() {
+                len[i + 1][j + 1] = if a[i] == b[j] {
+                    len[i][j] + 1
+                } else {
+                    len[i][j + 1].max(len[i + 1][j])
+                };
+            }
+        }
+        len[a.len()][b.len()]
+    }
+
+    #[test]
+    fn test3() {
+        let rules = [(0, 1), (0, 2), (1, 2)].into_iter().collect::<Rules>();
+        let r = rules.repair(&[1, 2, 3, 4, 0]).unwrap();
+        assert_eq!(r.moved, vec![0]);
+        assert_eq!(r.order, vec![0, 1, 2, 3, 4]);
+
+        let rules = [(3, 1)].into_iter().collect::<Rules>();
+        let r = rules.repair(&[1, 2, 3]).unwrap();
+        assert_eq!(r.moves(), 1);
+        assert!(rules.is_ordered(&r.order));
+
+        let mut seed = 3u64;
+        for _ in 0..300 {
+            let rank = permutations(&[0, 1, 2, 3, 4, 5])
+                .swap_remove((random(&mut seed) >> 40) as usize % 720);
+            let rules = (0..6)
+                .flat_map(|a| (0..6).map(move |b| (a, b)))
+                .filter(|&(a, b)| rank[a as usize] < rank[b as usize])
+                .filter(|_| random(&mut seed) >> 62 == 0)
+                .collect::<Rules>();
+            let mut pages = permutations(&[0, 1, 2, 3, 4, 5, 6])
+                .swap_remove((random(&mut seed) >> 40) as usize % 5040);
+            pages.truncate(3 + (random(&mut seed) >> 40) as usize % 5);
+
+            let r = rules.repair(&pages).unwrap();
+            let best = permutations(&pages)
+                .into_iter()
+                .filter(|p| rules.is_ordered(p))
+                .map(|p| common(&pages, &p))
+                .max()
+                .unwrap();
+            assert_eq!(r.moves(), pages.len() - best, "{pages:?}");
+            assert!(rules.is_ordered(&r.order));
+            assert_eq!(common(&r.kept, &r.order), r.kept.len());
+            assert_eq!(r.kept.len() + r.moved.len(), pages.len());
+        }
+    }
+}
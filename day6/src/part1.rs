@@ -61,7 +61,7 @@ fn parse(s: &str) -> (Grid<char>, Guard) {
 }
 
 pub fn solve(s: &str) -> usize {
-    let (grid, mut guard) = parse(s);
+    let (grid, mut guard) = memprof::phase("parse", || parse(s));
     let mut m = HashMap::<(i32, i32), bool>::new();
 
     loop {
@@ -117,7 +117,7 @@ mod tests {
 ........#.
 #.........
 ......#...";
-        assert_eq!(solve(&data), 41);
+        assert_eq!(solve(data), 41);
     }
 
     #[test]
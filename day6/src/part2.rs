@@ -1,5 +1,5 @@
 use grid::*;
-use std::collections::HashMap;
+use rayon::prelude::*;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum Direction {
@@ -60,107 +60,190 @@ fn parse(s: &str) -> (Grid<char>, Guard) {
     (grid, guard)
 }
 
-fn loop_found(guard: &Guard, grid: &Grid<char>, obstacle: (i32, i32)) -> bool {
-    let mut m = HashMap::<(i32, i32, Direction), bool>::new();
-    let mut guard = guard.clone();
+const EXIT: usize = usize::MAX;
 
-    loop {
-        let (r, c) = match guard.d {
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    fn index(self) -> usize {
+        match self {
+            Direction::Up => 0,
+            Direction::Right => 1,
+            Direction::Down => 2,
+            Direction::Left => 3,
+        }
+    }
+
+    fn delta(self) -> (i32, i32) {
+        match self {
             Direction::Up => (-1, 0),
             Direction::Down => (1, 0),
             Direction::Left => (0, -1),
             Direction::Right => (0, 1),
-        };
-
-        if guard.r + r < 0
-            || guard.c + c < 0
-            || guard.r + r >= grid.rows() as i32
-            || guard.c + c >= grid.cols() as i32
-        {
-            break;
         }
+    }
 
-        if m.contains_key(&(guard.r, guard.c, guard.d)) {
-            return true;
-        }
+    fn turn(self) -> Direction {
+        Direction::ALL[(self.index() + 1) % 4]
+    }
+}
 
-        m.insert((guard.r, guard.c, guard.d), true);
-        if let Some(next) = grid.get(guard.r + r, guard.c + c) {
-            if *next != '.' || (guard.r + r == obstacle.0 && guard.c + c == obstacle.1) {
-                guard.d = match guard.d {
-                    Direction::Up => Direction::Right,
-                    Direction::Down => Direction::Left,
-                    Direction::Left => Direction::Up,
-                    Direction::Right => Direction::Down,
-                };
-                continue;
+// jump[d][cell] is the cell where the guard stops when walking from cell in direction d, i.e. the
+// one just before the next obstacle, or EXIT when the guard walks off the map.
+struct Map {
+    cols: usize,
+    jump: [Vec<usize>; 4],
+}
+
+impl Map {
+    fn new(grid: &Grid<char>) -> Self {
+        let (rows, cols) = (grid.rows(), grid.cols());
+        let mut jump = [(); 4].map(|_| vec![EXIT; rows * cols]);
+        for d in Direction::ALL {
+            let (dr, dc) = d.delta();
+            let (lines, len) = if dr == 0 { (rows, cols) } else { (cols, rows) };
+            for line in 0..lines {
+                let mut stop = EXIT;
+                for k in 0..len {
+                    let k = if dr + dc < 0 { k } else { len - 1 - k };
+                    let (r, c) = if dr == 0 { (line, k) } else { (k, line) };
+                    if grid[(r, c)] == '#' {
+                        let r = r as i32 - dr;
+                        let c = c as i32 - dc;
+                        stop = if r < 0 || c < 0 || r >= rows as i32 || c >= cols as i32 {
+                            EXIT
+                        } else {
+                            r as usize * cols + c as usize
+                        };
+                    } else {
+                        jump[d.index()][r * cols + c] = stop;
+                    }
+                }
             }
-        } else {
-            unreachable!();
         }
+        Map { cols, jump }
+    }
 
-        guard.r += r;
-        guard.c += c;
+    // Jumps as if the extra obstacle was there: it only matters when it sits on the guard's line
+    // between the guard and the stop found in the table.
+    fn next(&self, cell: usize, d: Direction, obstacle: usize) -> usize {
+        let stop = self.jump[d.index()][cell];
+        let (r, c) = (cell / self.cols, cell % self.cols);
+        let (or, oc) = (obstacle / self.cols, obstacle % self.cols);
+        let before = match d {
+            Direction::Up if oc == c && or < r => obstacle + self.cols,
+            Direction::Down if oc == c && or > r => obstacle - self.cols,
+            Direction::Left if or == r && oc < c => obstacle + 1,
+            Direction::Right if or == r && oc > c => obstacle - 1,
+            _ => return stop,
+        };
+        let closer = stop == EXIT
+            || match d {
+                Direction::Up | Direction::Left => before > stop,
+                Direction::Down | Direction::Right => before < stop,
+            };
+        if closer {
+            before
+        } else {
+            stop
+        }
     }
-    false
 }
 
-fn get_positions(guard: &Guard, grid: &Grid<char>) -> Vec<(i32, i32)> {
-    let mut m = HashMap::<(i32, i32), bool>::new();
-    let mut guard = guard.clone();
-
-    loop {
-        let (r, c) = match guard.d {
-            Direction::Up => (-1, 0),
-            Direction::Down => (1, 0),
-            Direction::Left => (0, -1),
-            Direction::Right => (0, 1),
-        };
+struct Visited {
+    words: Vec<u64>,
+    touched: Vec<usize>,
+}
 
-        if guard.r + r < 0
-            || guard.c + c < 0
-            || guard.r + r >= grid.rows() as i32
-            || guard.c + c >= grid.cols() as i32
-        {
-            m.insert((guard.r, guard.c), true);
-            break;
-        }
-        m.insert((guard.r, guard.c), true);
-        if let Some(next) = grid.get(guard.r + r, guard.c + c) {
-            if *next != '.' {
-                guard.d = match guard.d {
-                    Direction::Up => Direction::Right,
-                    Direction::Down => Direction::Left,
-                    Direction::Left => Direction::Up,
-                    Direction::Right => Direction::Down,
-                };
-                continue;
-            }
-        } else {
-            unreachable!();
+impl Visited {
+    fn new(bits: usize) -> Self {
+        Visited {
+            words: vec![0; bits.div_ceil(64)],
+            touched: Vec::new(),
         }
+    }
 
-        guard.r += r;
-        guard.c += c;
+    fn insert(&mut self, bit: usize) -> bool {
+        let (w, mask) = (bit / 64, 1 << (bit % 64));
+        if self.words[w] == 0 {
+            self.touched.push(w);
+        }
+        let new = self.words[w] & mask == 0;
+        self.words[w] |= mask;
+        new
     }
 
-    m.keys().cloned().collect()
+    fn clear(&mut self) {
+        for w in self.touched.drain(..) {
+            self.words[w] = 0;
+        }
+    }
 }
 
-pub fn solve(s: &str) -> usize {
-    let (grid, guard) = parse(s);
-    let mut count = 0;
-
-    let positions = get_positions(&guard, &grid);
-    for p in positions {
-        if guard.r == p.0 && guard.c == p.1 {
-            continue;
+fn loop_found(
+    map: &Map,
+    visited: &mut Visited,
+    mut cell: usize,
+    mut d: Direction,
+    obstacle: usize,
+) -> bool {
+    visited.clear();
+    loop {
+        cell = map.next(cell, d, obstacle);
+        if cell == EXIT {
+            return false;
+        }
+        if !visited.insert(cell * 4 + d.index()) {
+            return true;
         }
-        if loop_found(&guard, &grid, p) {
-            count += 1;
+        d = d.turn();
+    }
+}
+
+// Every cell of the original path is a candidate, checked from the state just before the guard
+// first walks into it, since the path up to there does not change.
+fn get_candidates(guard: &Guard, grid: &Grid<char>) -> Vec<(usize, usize, Direction)> {
+    let cols = grid.cols();
+    let mut seen = vec![false; grid.rows() * cols];
+    let mut candidates = Vec::new();
+    let (mut r, mut c, mut d) = (guard.r, guard.c, guard.d);
+    seen[r as usize * cols + c as usize] = true;
+    loop {
+        let (dr, dc) = d.delta();
+        match grid.get(r + dr, c + dc) {
+            None => break,
+            Some('#') => d = d.turn(),
+            Some(_) => {
+                let from = r as usize * cols + c as usize;
+                r += dr;
+                c += dc;
+                let cell = r as usize * cols + c as usize;
+                if !seen[cell] {
+                    seen[cell] = true;
+                    candidates.push((cell, from, d));
+                }
+            }
         }
     }
-    count
+    candidates
+}
+
+pub fn solve(s: &str) -> usize {
+    let (grid, guard) = memprof::phase("parse", || parse(s));
+    let map = Map::new(&grid);
+    let bits = grid.rows() * grid.cols() * 4;
+    get_candidates(&guard, &grid)
+        .par_iter()
+        .map_init(
+            || Visited::new(bits),
+            |visited, &(obstacle, from, d)| loop_found(&map, visited, from, d, obstacle) as usize,
+        )
+        .sum()
 }
 
 #[cfg(test)]
@@ -179,7 +262,7 @@ mod tests {
 ........#.
 #.........
 ......#...";
-        assert_eq!(solve(&data), 6);
+        assert_eq!(solve(data), 6);
     }
 
     #[test]
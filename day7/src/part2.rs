@@ -38,7 +38,7 @@ fn recurse(v: &[u64], i: usize, res: u64, partial: u64) -> u64 {
 }
 
 pub fn solve(s: &str) -> u64 {
-    let (_, data) = parse(s).unwrap();
+    let (_, data) = memprof::phase("parse", || parse(s)).unwrap();
     data.iter().map(|(res, v)| recurse(v, 1, *res, v[0])).sum()
 }
 
@@ -57,7 +57,7 @@ mod tests {
 192: 17 8 14
 21037: 9 7 18 13
 292: 11 6 16 20";
-        assert_eq!(solve(&data), 11387);
+        assert_eq!(solve(data), 11387);
     }
 
     #[test]
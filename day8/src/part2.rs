@@ -30,7 +30,7 @@ fn parse(s: Span) -> IResult<Span, (HashMap<IVec2, char>, i32, i32)> {
 }
 
 pub fn solve(s: &str) -> usize {
-    let (_, (nodes, rows, cols)) = parse(Span::new(s)).unwrap();
+    let (_, (nodes, rows, cols)) = memprof::phase("parse", || parse(Span::new(s))).unwrap();
     let mut antinodes = HashSet::new();
     let xrange = 0..rows;
     let yrange = 0..cols;
@@ -78,7 +78,7 @@ mod tests {
 .........A..
 ............
 ............";
-        assert_eq!(solve(&data), 34);
+        assert_eq!(solve(data), 34);
     }
 
     #[test]
@@ -25,7 +25,7 @@ fn parse(s: &str) -> Vec<Block> {
 }
 
 pub fn solve(s: &str) -> usize {
-    let mut v = parse(s);
+    let mut v = memprof::phase("parse", || parse(s));
     let mut i = 0;
     let mut j = v.len() - 1;
     while i < j {
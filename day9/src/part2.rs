@@ -124,7 +124,7 @@ fn get_block_types(v: &[Block]) -> Vec<Type> {
 }
 
 pub fn solve(s: &str) -> usize {
-    let mut v = parse(s);
+    let mut v = memprof::phase("parse", || parse(s));
     loop {
         if reorder(&mut v) {
             break;
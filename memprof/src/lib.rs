@@ -0,0 +1,195 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static TAIL: AtomicUsize = AtomicUsize::new(0);
+static TAIL_BASE: AtomicUsize = AtomicUsize::new(0);
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static PHASES: Mutex<Vec<(&'static str, Stats)>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak: usize,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Report {
+    pub total: Stats,
+    pub phases: Vec<(&'static str, Stats)>,
+    pub tail_peak: usize,
+}
+
+impl Report {
+    pub fn phase(&self, name: &str) -> Stats {
+        self.phases
+            .iter()
+            .filter(|(n, _)| *n == name)
+            .fold(Stats::default(), |acc, (_, s)| Stats {
+                allocations: acc.allocations + s.allocations,
+                bytes: acc.bytes + s.bytes,
+                peak: acc.peak.max(s.peak),
+            })
+    }
+
+    // The peak of the remainder is taken after the last phase returned, on top of whatever that
+    // phase left allocated, so memory used while parsing does not show up again here.
+    pub fn remainder(&self, name: &str) -> Stats {
+        let phase = self.phase(name);
+        let peak = if self.phases.iter().any(|(n, _)| *n == name) {
+            self.tail_peak
+        } else {
+            self.total.peak
+        };
+        Stats {
+            allocations: self.total.allocations.saturating_sub(phase.allocations),
+            bytes: self.total.bytes.saturating_sub(phase.bytes),
+            peak,
+        }
+    }
+}
+
+pub struct Counting;
+
+fn grow(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+    TAIL.fetch_max(live, Ordering::Relaxed);
+}
+
+fn shrink(size: usize) {
+    LIVE.fetch_sub(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc(layout);
+        if !p.is_null() {
+            grow(layout.size());
+        }
+        p
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc_zeroed(layout);
+        if !p.is_null() {
+            grow(layout.size());
+        }
+        p
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let p = System.realloc(ptr, layout, new_size);
+        if !p.is_null() {
+            shrink(layout.size());
+            grow(new_size);
+        }
+        p
+    }
+}
+
+// peak is tracked as a single global high-water mark: save the outer one, measure from the
+// current live size, then fold the inner peak back so nested measurements stay correct.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Stats) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    let outer = PEAK.swap(live, Ordering::Relaxed);
+    let t = f();
+    let peak = PEAK.fetch_max(outer, Ordering::Relaxed);
+    let stats = Stats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: peak.saturating_sub(live),
+    };
+    (t, stats)
+}
+
+pub fn phase<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    if !RECORDING.load(Ordering::Relaxed) {
+        return f();
+    }
+    let (t, stats) = measure(f);
+    PHASES.lock().unwrap().push((name, stats));
+    restart_tail();
+    t
+}
+
+fn restart_tail() {
+    let live = LIVE.load(Ordering::Relaxed);
+    TAIL_BASE.store(live, Ordering::Relaxed);
+    TAIL.store(live, Ordering::Relaxed);
+}
+
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Report) {
+    PHASES.lock().unwrap().clear();
+    RECORDING.store(true, Ordering::Relaxed);
+    restart_tail();
+    let (t, total) = measure(f);
+    RECORDING.store(false, Ordering::Relaxed);
+    let tail_peak = TAIL
+        .load(Ordering::Relaxed)
+        .saturating_sub(TAIL_BASE.load(Ordering::Relaxed));
+    let phases = std::mem::take(&mut *PHASES.lock().unwrap());
+    (
+        t,
+        Report {
+            total,
+            phases,
+            tail_peak,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test1() {
+        let _lock = LOCK.lock().unwrap();
+        let (_, report) = record(|| {
+            let v = phase("parse", || vec![0u8; 1000]);
+            let w = vec![0u8; 4000];
+            v.len() + w.len()
+        });
+        let parse = report.phase("parse");
+        assert!(parse.allocations >= 1);
+        assert!(parse.bytes >= 1000);
+        assert!(parse.peak >= 1000);
+        assert!(report.total.bytes >= 5000);
+        assert!(report.total.peak >= 5000);
+        assert!(report.remainder("parse").bytes >= 4000);
+    }
+
+    #[test]
+    fn test2() {
+        let _lock = LOCK.lock().unwrap();
+        let (_, report) = record(|| {
+            let v = phase("parse", || vec![0u8; 100_000]);
+            let w = vec![0u8; 4000];
+            v.len() + w.len()
+        });
+        assert!(report.phase("parse").peak >= 100_000);
+        let solve = report.remainder("parse");
+        assert!(solve.peak >= 4000);
+        assert!(solve.peak < 100_000);
+    }
+}
@@ -0,0 +1,37 @@
+use runner::days::{self, DAYS};
+
+fn main() {
+    divan::main();
+}
+
+#[divan::bench(args = DAYS.iter().map(|d| d.name), sample_count = 3, sample_size = 1)]
+fn part1(bencher: divan::Bencher, name: &str) {
+    let day = days::find(name).unwrap();
+    let data = day.input();
+    bencher.bench(|| (day.part1)(&data));
+}
+
+#[divan::bench(
+    args = DAYS.iter().filter(|d| d.part2.is_some()).map(|d| d.name),
+    sample_count = 3,
+    sample_size = 1
+)]
+fn part2(bencher: divan::Bencher, name: &str) {
+    let day = days::find(name).unwrap();
+    let part2 = day.part2.unwrap();
+    let data = day.input();
+    bencher.bench(|| part2(&data));
+}
+
+#[divan::bench(sample_count = 1, sample_size = 1)]
+fn all(bencher: divan::Bencher) {
+    let inputs = DAYS.iter().map(|d| d.input()).collect::<Vec<_>>();
+    bencher.bench(|| {
+        for (day, data) in DAYS.iter().zip(&inputs) {
+            (day.part1)(data);
+            if let Some(part2) = day.part2 {
+                part2(data);
+            }
+        }
+    });
+}
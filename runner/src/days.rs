@@ -0,0 +1,152 @@
+use std::path::{Path, PathBuf};
+
+pub struct Day {
+    pub name: &'static str,
+    pub part1: fn(&str) -> String,
+    pub part2: Option<fn(&str) -> String>,
+}
+
+impl Day {
+    pub fn input_path(&self) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(self.name)
+            .join("input/input.txt")
+    }
+
+    pub fn input(&self) -> String {
+        std::fs::read_to_string(self.input_path()).expect("input should exist")
+    }
+}
+
+pub fn find(name: &str) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.name == name)
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        name: "day1",
+        part1: |s| day1::part1::solve(s).to_string(),
+        part2: Some(|s| day1::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day2",
+        part1: |s| day2::part1::solve(s).to_string(),
+        part2: Some(|s| day2::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day3",
+        part1: |s| day3::part1::solve(s).to_string(),
+        part2: Some(|s| day3::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day4",
+        part1: |s| day4::part1::solve(s).to_string(),
+        part2: Some(|s| day4::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day5",
+        part1: |s| day5::part1::solve(s).to_string(),
+        part2: Some(|s| day5::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day6",
+        part1: |s| day6::part1::solve(s).to_string(),
+        part2: Some(|s| day6::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day7",
+        part1: |s| day7::part1::solve(s).to_string(),
+        part2: Some(|s| day7::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day8",
+        part1: |s| day8::part1::solve(s).to_string(),
+        part2: Some(|s| day8::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day9",
+        part1: |s| day9::part1::solve(s).to_string(),
+        part2: Some(|s| day9::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day10",
+        part1: |s| day10::part1::solve(s).to_string(),
+        part2: Some(|s| day10::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day11",
+        part1: |s| day11::part1::solve(s, 25).to_string(),
+        part2: Some(|s| day11::part2::solve(s, 75).to_string()),
+    },
+    Day {
+        name: "day12",
+        part1: |s| day12::part1::solve(s).to_string(),
+        part2: Some(|s| day12::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day13",
+        part1: |s| day13::part1::solve(s).to_string(),
+        part2: Some(|s| day13::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day14",
+        part1: |s| day14::part1::solve(s, 103, 101).to_string(),
+        part2: Some(|s| day14::part2::solve(s, 103, 101).to_string()),
+    },
+    Day {
+        name: "day15",
+        part1: |s| day15::part1::solve(s).to_string(),
+        part2: Some(|s| day15::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day16",
+        part1: |s| day16::part1::solve(s).to_string(),
+        part2: Some(|s| day16::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day17",
+        part1: day17::part1::solve,
+        part2: Some(|s| day17::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day18",
+        part1: |s| day18::part1::solve(s, 71, 71, 1024).to_string(),
+        part2: Some(|s| day18::part2::solve(s, 71, 71)),
+    },
+    Day {
+        name: "day19",
+        part1: |s| day19::part1::solve(s).to_string(),
+        part2: Some(|s| day19::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day20",
+        part1: |s| day20::part1::solve(s).to_string(),
+        part2: Some(|s| day20::part2::solve(s, 20, 100).to_string()),
+    },
+    Day {
+        name: "day21",
+        part1: |s| day21::part1::solve(s).to_string(),
+        part2: Some(|s| day21::part2::solve(s, 25).to_string()),
+    },
+    Day {
+        name: "day22",
+        part1: |s| day22::part1::solve(s).to_string(),
+        part2: Some(|s| day22::part2::solve(s).to_string()),
+    },
+    Day {
+        name: "day23",
+        part1: |s| day23::part1::solve(s).to_string(),
+        part2: Some(day23::part2::solve),
+    },
+    Day {
+        name: "day24",
+        part1: |s| day24::part1::solve(s).to_string(),
+        part2: Some(day24::part2::solve),
+    },
+    Day {
+        name: "day25",
+        part1: |s| day25::part1::solve(s).to_string(),
+        part2: None,
+    },
+];
@@ -0,0 +1,130 @@
+use memprof::Stats;
+use runner::days::{self, Day, DAYS};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "alloc-profile")]
+#[global_allocator]
+static GLOBAL: memprof::Counting = memprof::Counting;
+
+const USAGE: &str = "usage: aoc [--alloc] [--json <path>] [day...]";
+
+#[derive(Debug, Default)]
+struct Args {
+    alloc: bool,
+    json: Option<PathBuf>,
+    days: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct PhaseResult {
+    phase: &'static str,
+    allocations: usize,
+    bytes: usize,
+    peak: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct PartResult {
+    day: &'static str,
+    part: u8,
+    answer: String,
+    micros: u128,
+    phases: Vec<PhaseResult>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut it = std::env::args().skip(1);
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--alloc" => args.alloc = true,
+            "--json" => args.json = Some(it.next().ok_or("--json needs a path")?.into()),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if arg.starts_with('-') => return Err(format!("unknown flag: {arg}\n{USAGE}")),
+            _ if arg.starts_with("day") => args.days.push(arg),
+            _ => args.days.push(format!("day{arg}")),
+        }
+    }
+    if args.alloc && !cfg!(feature = "alloc-profile") {
+        return Err("--alloc needs the runner built with --features alloc-profile".to_string());
+    }
+    Ok(args)
+}
+
+fn phase_result(phase: &'static str, stats: Stats) -> PhaseResult {
+    PhaseResult {
+        phase,
+        allocations: stats.allocations,
+        bytes: stats.bytes,
+        peak: stats.peak,
+    }
+}
+
+fn run_part(day: &Day, part: u8, f: fn(&str) -> String, input: &str, alloc: bool) -> PartResult {
+    let start = Instant::now();
+    let (answer, phases) = if alloc {
+        let (answer, report) = memprof::record(|| f(input));
+        let phases = vec![
+            phase_result("parse", report.phase("parse")),
+            phase_result("solve", report.remainder("parse")),
+        ];
+        (answer, phases)
+    } else {
+        (f(input), Vec::new())
+    };
+    let elapsed = start.elapsed();
+    print_part(day, part, &answer, elapsed, &phases);
+    PartResult {
+        day: day.name,
+        part,
+        answer,
+        micros: elapsed.as_micros(),
+        phases,
+    }
+}
+
+fn print_part(day: &Day, part: u8, answer: &str, elapsed: Duration, phases: &[PhaseResult]) {
+    println!("{:<6} part{part}  {answer:<20} {elapsed:>12.2?}", day.name);
+    for p in phases {
+        println!(
+            "{:<6} {:<6} {:>10} allocs {:>14} bytes {:>14} peak",
+            "", p.phase, p.allocations, p.bytes, p.peak
+        );
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+    if let Some(unknown) = args.days.iter().find(|name| days::find(name).is_none()) {
+        eprintln!("unknown day: {unknown}");
+        std::process::exit(2);
+    }
+
+    let mut results = Vec::new();
+    for day in DAYS
+        .iter()
+        .filter(|d| args.days.is_empty() || args.days.iter().any(|n| n == d.name))
+    {
+        let input = day.input();
+        results.push(run_part(day, 1, day.part1, &input, args.alloc));
+        if let Some(part2) = day.part2 {
+            results.push(run_part(day, 2, part2, &input, args.alloc));
+        }
+    }
+
+    let total = Duration::from_micros(results.iter().map(|r| r.micros as u64).sum());
+    println!("{:<6} {:<6} {:<20} {total:>12.2?}", "total", "", "");
+
+    if let Some(path) = args.json {
+        let json = serde_json::to_string_pretty(&results).expect("results should serialize");
+        std::fs::write(&path, json).expect("json should be writable");
+    }
+}